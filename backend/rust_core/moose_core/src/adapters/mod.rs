@@ -13,6 +13,7 @@ pub use openai::OpenAICompatBackend;
 use std::collections::HashMap;
 
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
 use thiserror::Error;
 use tokio::sync::mpsc;
 
@@ -45,6 +46,18 @@ impl From<AdapterError> for pyo3::PyErr {
     }
 }
 
+/// Default number of chunk-embedding requests kept in flight at once when
+/// the caller doesn't specify `max_concurrency`.
+const DEFAULT_EMBED_CONCURRENCY: usize = 4;
+
+/// Result of a streamed chat completion: the assembled text plus any tool
+/// calls accumulated from per-chunk deltas across the stream.
+#[derive(Debug, Clone, Default)]
+pub struct StreamCompletion {
+    pub content: String,
+    pub tool_calls: Vec<serde_json::Value>,
+}
+
 /// Trait for inference backend implementations
 #[async_trait]
 pub trait InferenceBackend: Send + Sync {
@@ -59,15 +72,46 @@ pub trait InferenceBackend: Send + Sync {
         &self,
         request: LlmRequest,
         tx: mpsc::Sender<String>,
-    ) -> Result<String, AdapterError>;
+    ) -> Result<StreamCompletion, AdapterError>;
 
-    /// Generate embeddings
-    async fn embed(
+    /// Suggested number of texts per embedding request, used as the default
+    /// `chunk_size` in `embed` when the caller doesn't specify one. Backends
+    /// with a smaller practical batch limit should override this.
+    fn chunk_count_hint(&self) -> usize {
+        64
+    }
+
+    /// Generate embeddings for a single request-sized batch of texts.
+    async fn embed_chunk(
         &self,
         model_id: &str,
         texts: &[String],
     ) -> Result<Vec<Vec<f32>>, AdapterError>;
 
+    /// Generate embeddings for `texts`, splitting into `chunk_size`-sized
+    /// chunks (default: `chunk_count_hint()`) and dispatching up to
+    /// `max_concurrency` (default `DEFAULT_EMBED_CONCURRENCY`) chunk requests
+    /// at once. Reassembles results in the original input order; a failure
+    /// in any chunk aborts the rest and surfaces as the first error.
+    async fn embed(
+        &self,
+        model_id: &str,
+        texts: &[String],
+        chunk_size: Option<usize>,
+        max_concurrency: Option<usize>,
+    ) -> Result<Vec<Vec<f32>>, AdapterError> {
+        let chunk_size = chunk_size.unwrap_or_else(|| self.chunk_count_hint()).max(1);
+        let max_concurrency = max_concurrency.unwrap_or(DEFAULT_EMBED_CONCURRENCY).max(1);
+
+        let chunked: Vec<Vec<Vec<f32>>> = stream::iter(texts.chunks(chunk_size))
+            .map(|chunk| self.embed_chunk(model_id, chunk))
+            .buffered(max_concurrency)
+            .try_collect()
+            .await?;
+
+        Ok(chunked.into_iter().flatten().collect())
+    }
+
     /// Load a model into memory
     async fn load_model(
         &self,
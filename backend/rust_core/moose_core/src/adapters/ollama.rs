@@ -10,7 +10,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
-use super::{AdapterError, InferenceBackend};
+use super::{AdapterError, InferenceBackend, StreamCompletion};
 use crate::router::{LlmRequest, LlmResponse, ModelInfo, UsageInfo};
 
 /// Ollama models list response
@@ -72,6 +72,37 @@ struct OllamaStreamChunk {
 struct OllamaStreamMessage {
     #[serde(default)]
     content: String,
+    #[serde(default)]
+    tool_calls: Vec<OllamaStreamToolCall>,
+}
+
+/// A tool-call fragment on a streamed chunk. `arguments` may arrive split
+/// across several chunks that share the same `index`.
+#[derive(Debug, Deserialize)]
+struct OllamaStreamToolCall {
+    #[serde(default)]
+    index: usize,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<OllamaStreamFunctionCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaStreamFunctionCall {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+/// Tool-call fragments accumulated across chunks for a single `index`,
+/// until `arguments` is a complete JSON string.
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: Option<String>,
+    name: Option<String>,
+    arguments: String,
 }
 
 /// Ollama embedding response
@@ -216,7 +247,7 @@ impl InferenceBackend for OllamaBackend {
         &self,
         request: LlmRequest,
         tx: mpsc::Sender<String>,
-    ) -> Result<String, AdapterError> {
+    ) -> Result<StreamCompletion, AdapterError> {
         let url = format!("{}/api/chat", self.base_url.trim_end_matches('/'));
 
         let messages = Self::convert_messages(&request.messages);
@@ -252,6 +283,7 @@ impl InferenceBackend for OllamaBackend {
         let mut stream = response.bytes_stream();
         let mut full_content = String::new();
         let mut buffer = String::new();
+        let mut partial_calls: std::collections::BTreeMap<usize, PartialToolCall> = std::collections::BTreeMap::new();
 
         while let Some(chunk_result) = stream.next().await {
             let chunk = chunk_result?;
@@ -273,6 +305,23 @@ impl InferenceBackend for OllamaBackend {
                             full_content.push_str(&message.content);
                             let _ = tx.send(message.content).await;
                         }
+
+                        // Accumulate tool-call fragments by index; `arguments`
+                        // may span multiple NDJSON lines before it's complete.
+                        for tc in message.tool_calls {
+                            let entry = partial_calls.entry(tc.index).or_default();
+                            if let Some(id) = tc.id {
+                                entry.id = Some(id);
+                            }
+                            if let Some(function) = tc.function {
+                                if let Some(name) = function.name {
+                                    entry.name = Some(name);
+                                }
+                                if let Some(arguments) = function.arguments {
+                                    entry.arguments.push_str(&arguments);
+                                }
+                            }
+                        }
                     }
 
                     if chunk.done {
@@ -282,10 +331,34 @@ impl InferenceBackend for OllamaBackend {
             }
         }
 
-        Ok(full_content)
+        let mut tool_calls = Vec::with_capacity(partial_calls.len());
+        for (index, call) in partial_calls {
+            serde_json::from_str::<serde_json::Value>(&call.arguments).map_err(|e| {
+                AdapterError::StreamError(format!(
+                    "tool call at index {index} has incomplete or invalid JSON arguments: {e}"
+                ))
+            })?;
+            tool_calls.push(serde_json::json!({
+                "id": call.id.unwrap_or_else(|| format!("call_{index}")),
+                "type": "function",
+                "function": {
+                    "name": call.name.unwrap_or_default(),
+                    "arguments": call.arguments,
+                }
+            }));
+        }
+
+        Ok(StreamCompletion { content: full_content, tool_calls })
+    }
+
+    fn chunk_count_hint(&self) -> usize {
+        // Ollama's /api/embed holds the whole batch in memory for one model
+        // generation pass, so keep chunks smaller than the default to avoid
+        // stalling on large inputs.
+        32
     }
 
-    async fn embed(
+    async fn embed_chunk(
         &self,
         model_id: &str,
         texts: &[String],
@@ -10,7 +10,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
-use super::{AdapterError, InferenceBackend};
+use super::{AdapterError, InferenceBackend, StreamCompletion};
 use crate::router::{LlmRequest, LlmResponse, ModelInfo, UsageInfo};
 
 /// OpenAI models list response
@@ -278,7 +278,7 @@ impl InferenceBackend for OpenAICompatBackend {
         &self,
         request: LlmRequest,
         tx: mpsc::Sender<String>,
-    ) -> Result<String, AdapterError> {
+    ) -> Result<StreamCompletion, AdapterError> {
         let url = format!("{}/v1/chat/completions", self.base_url.trim_end_matches('/'));
 
         let mut body = serde_json::json!({
@@ -342,10 +342,10 @@ impl InferenceBackend for OpenAICompatBackend {
             }
         }
 
-        Ok(full_content)
+        Ok(StreamCompletion { content: full_content, tool_calls: Vec::new() })
     }
 
-    async fn embed(
+    async fn embed_chunk(
         &self,
         model_id: &str,
         texts: &[String],
@@ -2,14 +2,20 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use chrono::{Duration, Utc};
+use arrow::array::{ArrayRef, Float64Array, StringArray, TimestampMicrosecondArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::pyarrow::PyArrowType;
+use arrow::record_batch::RecordBatch;
+use chrono::{DateTime, Duration, Utc};
 use parking_lot::Mutex;
 use pyo3::prelude::*;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::telemetry;
+
 const DEFAULT_DECAY_RATE: f64 = 0.05;
 const MIN_IMPORTANCE_THRESHOLD: f64 = 0.1;
 const DEFAULT_MIN_AGE_DAYS: u64 = 30;
@@ -57,10 +63,23 @@ impl EpisodicMemory {
                 id TEXT PRIMARY KEY, content TEXT NOT NULL, memory_type TEXT NOT NULL,
                 domain TEXT, importance REAL NOT NULL DEFAULT 1.0, access_count INTEGER NOT NULL DEFAULT 0,
                 last_accessed TEXT NOT NULL, created_at TEXT NOT NULL, updated_at TEXT NOT NULL,
-                entity_type TEXT, entity_id TEXT, supersedes TEXT, superseded_by TEXT, metadata TEXT NOT NULL DEFAULT '{}'
+                entity_type TEXT, entity_id TEXT, supersedes TEXT, superseded_by TEXT, metadata TEXT NOT NULL DEFAULT '{}',
+                causality_token TEXT NOT NULL DEFAULT '1'
             );
             CREATE INDEX IF NOT EXISTS idx_episodic_memory_type ON episodic_memories(memory_type);
             CREATE INDEX IF NOT EXISTS idx_episodic_importance ON episodic_memories(importance);
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS episodic_fts USING fts5(id UNINDEXED, content);
+            CREATE TRIGGER IF NOT EXISTS episodic_fts_ai AFTER INSERT ON episodic_memories BEGIN
+                INSERT INTO episodic_fts(id, content) VALUES (new.id, new.content);
+            END;
+            CREATE TRIGGER IF NOT EXISTS episodic_fts_ad AFTER DELETE ON episodic_memories BEGIN
+                DELETE FROM episodic_fts WHERE id = old.id;
+            END;
+            CREATE TRIGGER IF NOT EXISTS episodic_fts_au AFTER UPDATE OF content ON episodic_memories BEGIN
+                DELETE FROM episodic_fts WHERE id = old.id;
+                INSERT INTO episodic_fts(id, content) VALUES (new.id, new.content);
+            END;
         "#)?;
         Ok(())
     }
@@ -77,49 +96,140 @@ impl EpisodicMemory {
     }
 
     #[pyo3(signature = (content, memory_type, domain=None, importance=None))]
+    #[tracing::instrument(skip(self, content), fields(memory_type = %memory_type))]
     fn store(&self, content: String, memory_type: String, domain: Option<String>, importance: Option<f64>) -> PyResult<String> {
         let id = Uuid::new_v4().to_string()[..12].to_string();
         let now = Utc::now().to_rfc3339();
         let importance = importance.unwrap_or(1.0);
+        let start = std::time::Instant::now();
         let inner = self.inner.lock();
         inner.conn.execute(
             "INSERT INTO episodic_memories (id, content, memory_type, domain, importance, access_count, last_accessed, created_at, updated_at, metadata) VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6, ?6, '{}')",
             params![id, content, memory_type, domain, importance, now],
         ).map_err(EpisodicMemoryError::from)?;
+        telemetry::record_query_latency("episodic.store", start.elapsed());
         Ok(id)
     }
 
-    #[pyo3(signature = (query, top_k=None))]
-    fn search(&self, query: String, top_k: Option<usize>) -> PyResult<Vec<HashMap<String, String>>> {
+    /// Stores several memories as a single SQLite transaction. Each entry is a
+    /// map with the same keys as `store`'s arguments (`content` and
+    /// `memory_type` required, `domain`/`importance` optional). Returns
+    /// `(id, causality_token)` pairs in the same order as `entries`.
+    #[tracing::instrument(skip(self, entries))]
+    fn store_batch(&self, entries: Vec<HashMap<String, String>>) -> PyResult<Vec<(String, String)>> {
+        let start = std::time::Instant::now();
+        let inner = self.inner.lock();
+        inner.conn.execute_batch("BEGIN")?;
+        let mut results = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let content = entry.get("content").cloned().unwrap_or_default();
+            let memory_type = entry.get("memory_type").cloned().unwrap_or_default();
+            let domain = entry.get("domain").cloned();
+            let importance: f64 = entry.get("importance").and_then(|v| v.parse().ok()).unwrap_or(1.0);
+            let id = Uuid::new_v4().to_string()[..12].to_string();
+            let now = Utc::now().to_rfc3339();
+            let token = "1".to_string();
+            if let Err(e) = inner.conn.execute(
+                "INSERT INTO episodic_memories (id, content, memory_type, domain, importance, access_count, last_accessed, created_at, updated_at, metadata, causality_token) VALUES (?1, ?2, ?3, ?4, ?5, 0, ?6, ?6, ?6, '{}', ?7)",
+                params![id, content, memory_type, domain, importance, now, token],
+            ) {
+                inner.conn.execute_batch("ROLLBACK")?;
+                return Err(EpisodicMemoryError::from(e).into());
+            }
+            results.push((id, token));
+        }
+        inner.conn.execute_batch("COMMIT")?;
+        telemetry::record_query_latency("episodic.store_batch", start.elapsed());
+        Ok(results)
+    }
+
+    /// Optimistic-concurrency update: succeeds only if `token` matches the
+    /// memory's current `causality_token`, in which case the token is bumped
+    /// and `(true, content, new_token)` is returned. On a stale token the
+    /// memory is left untouched and `(false, current_content, current_token)`
+    /// is returned so the caller can re-read and retry.
+    fn update(&self, id: String, content: String, token: String) -> PyResult<(bool, String, String)> {
+        let inner = self.inner.lock();
+        let current: Option<(String, String)> = inner.conn.query_row(
+            "SELECT content, causality_token FROM episodic_memories WHERE id = ?1",
+            params![id], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional().map_err(EpisodicMemoryError::from)?;
+        let Some((current_content, current_token)) = current else {
+            return Ok((false, String::new(), String::new()));
+        };
+        if current_token != token {
+            return Ok((false, current_content, current_token));
+        }
+        let next_token = current_token.parse::<u64>().map(|n| (n + 1).to_string()).unwrap_or_else(|_| Uuid::new_v4().to_string());
+        let now = Utc::now().to_rfc3339();
+        inner.conn.execute(
+            "UPDATE episodic_memories SET content = ?1, causality_token = ?2, updated_at = ?3 WHERE id = ?4",
+            params![content, next_token, now, id],
+        ).map_err(EpisodicMemoryError::from)?;
+        Ok((true, content, next_token))
+    }
+
+    /// Ranked recall over stored memories. By default runs `query` as an FTS5 MATCH
+    /// expression (supports `AND`/`OR`/`NEAR`/prefix `*`) and orders by a blend of
+    /// BM25 relevance and importance, so important memories float up even when they
+    /// match less tightly. Pass `exact=True` to fall back to a plain substring scan.
+    #[pyo3(signature = (query, top_k=None, exact=None))]
+    #[tracing::instrument(skip(self, query))]
+    fn search(&self, query: String, top_k: Option<usize>, exact: Option<bool>) -> PyResult<Vec<HashMap<String, String>>> {
         let top_k = top_k.unwrap_or(10);
+        let start = std::time::Instant::now();
         let inner = self.inner.lock();
-        let query_pattern = format!("%{}%", query);
-        let mut stmt = inner.conn.prepare("SELECT id, content, memory_type, importance FROM episodic_memories WHERE content LIKE ?1 AND superseded_by IS NULL ORDER BY importance DESC LIMIT ?2").map_err(EpisodicMemoryError::from)?;
-        let results: Vec<HashMap<String, String>> = stmt.query_map(params![query_pattern, top_k as i64], |row| {
-            let mut map = HashMap::new();
-            map.insert("id".to_string(), row.get::<_, String>(0)?);
-            map.insert("content".to_string(), row.get::<_, String>(1)?);
-            map.insert("memory_type".to_string(), row.get::<_, String>(2)?);
-            map.insert("importance".to_string(), row.get::<_, f64>(3)?.to_string());
-            Ok(map)
-        }).map_err(EpisodicMemoryError::from)?.filter_map(|r| r.ok()).collect();
+        let results: Vec<HashMap<String, String>> = if exact.unwrap_or(false) {
+            let query_pattern = format!("%{}%", query);
+            let mut stmt = inner.conn.prepare("SELECT id, content, memory_type, importance FROM episodic_memories WHERE content LIKE ?1 AND superseded_by IS NULL ORDER BY importance DESC LIMIT ?2").map_err(EpisodicMemoryError::from)?;
+            stmt.query_map(params![query_pattern, top_k as i64], |row| {
+                let mut map = HashMap::new();
+                map.insert("id".to_string(), row.get::<_, String>(0)?);
+                map.insert("content".to_string(), row.get::<_, String>(1)?);
+                map.insert("memory_type".to_string(), row.get::<_, String>(2)?);
+                map.insert("importance".to_string(), row.get::<_, f64>(3)?.to_string());
+                Ok(map)
+            }).map_err(EpisodicMemoryError::from)?.filter_map(|r| r.ok()).collect()
+        } else {
+            let mut stmt = inner.conn.prepare(
+                "SELECT m.id, m.content, m.memory_type, m.importance FROM episodic_fts f \
+                 JOIN episodic_memories m ON m.id = f.id \
+                 WHERE episodic_fts MATCH ?1 AND m.superseded_by IS NULL \
+                 ORDER BY bm25(episodic_fts) * (1 + m.importance) LIMIT ?2"
+            ).map_err(EpisodicMemoryError::from)?;
+            stmt.query_map(params![query, top_k as i64], |row| {
+                let mut map = HashMap::new();
+                map.insert("id".to_string(), row.get::<_, String>(0)?);
+                map.insert("content".to_string(), row.get::<_, String>(1)?);
+                map.insert("memory_type".to_string(), row.get::<_, String>(2)?);
+                map.insert("importance".to_string(), row.get::<_, f64>(3)?.to_string());
+                Ok(map)
+            }).map_err(EpisodicMemoryError::from)?.filter_map(|r| r.ok()).collect()
+        };
+        telemetry::record_query_latency("episodic.search", start.elapsed());
+        telemetry::record_rows_returned("episodic.search", results.len());
         Ok(results)
     }
 
     #[pyo3(signature = (decay_rate=None))]
+    #[tracing::instrument(skip(self))]
     fn decay_importance(&self, decay_rate: Option<f64>) -> PyResult<u64> {
         let decay = decay_rate.unwrap_or(DEFAULT_DECAY_RATE);
+        let start = std::time::Instant::now();
         let inner = self.inner.lock();
         let count = inner.conn.execute("UPDATE episodic_memories SET importance = importance * ?1", params![1.0 - decay]).map_err(EpisodicMemoryError::from)?;
+        telemetry::record_query_latency("episodic.decay_importance", start.elapsed());
         Ok(count as u64)
     }
 
     #[pyo3(signature = (min_age_days=None, min_importance=None))]
+    #[tracing::instrument(skip(self))]
     fn evict_low_importance(&self, min_age_days: Option<u64>, min_importance: Option<f64>) -> PyResult<u64> {
         let min_importance = min_importance.unwrap_or(MIN_IMPORTANCE_THRESHOLD);
         let cutoff = Utc::now() - Duration::days(min_age_days.unwrap_or(DEFAULT_MIN_AGE_DAYS) as i64);
         let inner = self.inner.lock();
         let count = inner.conn.execute("DELETE FROM episodic_memories WHERE importance < ?1 AND created_at < ?2 AND superseded_by IS NULL", params![min_importance, cutoff.to_rfc3339()]).map_err(EpisodicMemoryError::from)?;
+        telemetry::record_memories_evicted(count as u64, "episodic");
         Ok(count as u64)
     }
 
@@ -134,4 +244,66 @@ impl EpisodicMemory {
         inner.conn.execute("DELETE FROM episodic_memories", []).map_err(EpisodicMemoryError::from)?;
         Ok(())
     }
+
+    /// Exports memories matching the optional filter as a single Arrow
+    /// `RecordBatch` with typed columns (`importance`/`access_count` as
+    /// numeric arrays, `created_at` as an Arrow timestamp), returned to
+    /// Python zero-copy via the Arrow C Data Interface (pyarrow). Filters are
+    /// pushed down into the SQL `WHERE` clause so only matching rows are ever
+    /// materialized.
+    #[pyo3(signature = (memory_type=None, domain=None, min_importance=None, max_importance=None))]
+    fn export_arrow(&self, memory_type: Option<String>, domain: Option<String>, min_importance: Option<f64>, max_importance: Option<f64>) -> PyResult<PyArrowType<RecordBatch>> {
+        let inner = self.inner.lock();
+        let mut sql = "SELECT id, content, memory_type, domain, importance, access_count, created_at FROM episodic_memories WHERE 1=1".to_string();
+        let mut query_params: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(ref mt) = memory_type { sql.push_str(" AND memory_type = ?"); query_params.push(Box::new(mt.clone())); }
+        if let Some(ref d) = domain { sql.push_str(" AND domain = ?"); query_params.push(Box::new(d.clone())); }
+        if let Some(min_i) = min_importance { sql.push_str(" AND importance >= ?"); query_params.push(Box::new(min_i)); }
+        if let Some(max_i) = max_importance { sql.push_str(" AND importance <= ?"); query_params.push(Box::new(max_i)); }
+        sql.push_str(" ORDER BY created_at ASC");
+
+        let mut stmt = inner.conn.prepare(&sql).map_err(EpisodicMemoryError::from)?;
+        let param_refs: Vec<&dyn ToSql> = query_params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt.query_map(param_refs.as_slice(), |row| {
+            Ok((
+                row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?, row.get::<_, f64>(4)?, row.get::<_, i64>(5)?, row.get::<_, String>(6)?,
+            ))
+        }).map_err(EpisodicMemoryError::from)?;
+
+        let (mut ids, mut contents, mut memory_types, mut domains, mut importances, mut access_counts, mut created_ats) =
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for row in rows {
+            let (id, content, memory_type, domain, importance, access_count, created_at) = row.map_err(EpisodicMemoryError::from)?;
+            ids.push(id);
+            contents.push(content);
+            memory_types.push(memory_type);
+            domains.push(domain);
+            importances.push(importance);
+            access_counts.push(access_count as u64);
+            created_ats.push(DateTime::parse_from_rfc3339(&created_at).map(|t| t.timestamp_micros()).unwrap_or(0));
+        }
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("content", DataType::Utf8, false),
+            Field::new("memory_type", DataType::Utf8, false),
+            Field::new("domain", DataType::Utf8, true),
+            Field::new("importance", DataType::Float64, false),
+            Field::new("access_count", DataType::UInt64, false),
+            Field::new("created_at", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+        ]));
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(ids)),
+            Arc::new(StringArray::from(contents)),
+            Arc::new(StringArray::from(memory_types)),
+            Arc::new(StringArray::from(domains)),
+            Arc::new(Float64Array::from(importances)),
+            Arc::new(UInt64Array::from(access_counts)),
+            Arc::new(TimestampMicrosecondArray::from(created_ats)),
+        ];
+        let batch = RecordBatch::try_new(schema, columns)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Arrow batch construction error: {e}")))?;
+        Ok(PyArrowType(batch))
+    }
 }
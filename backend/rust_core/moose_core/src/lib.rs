@@ -2,10 +2,13 @@
 
 use pyo3::prelude::*;
 
+pub mod adapters;
 pub mod episodic;
 pub mod messages;
 pub mod router;
 pub mod scheduler;
+pub mod stores;
+pub mod telemetry;
 pub mod vector;
 pub mod workspace;
 
@@ -17,6 +20,8 @@ fn moose_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<workspace::SharedWorkspace>()?;
     m.add_class::<scheduler::Scheduler>()?;
     m.add_class::<router::InferenceRouter>()?;
+    m.add_class::<router::ServerHandle>()?;
+    m.add_function(wrap_pyfunction!(telemetry::init_telemetry, m)?)?;
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     Ok(())
 }
@@ -2,11 +2,30 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use pyo3::prelude::*;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::sync::Mutex as TokioMutex;
+use tokio::sync::{oneshot, Mutex as TokioMutex};
+
+/// Consecutive failures after which a backend is temporarily ejected from selection.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// How long an ejected backend is skipped before it's eligible again.
+const EJECTION_DURATION: Duration = Duration::from_secs(30);
+/// Base delay for exponential backoff between failover attempts.
+const BASE_BACKOFF_MS: u64 = 100;
+/// Default number of texts per embedding request when `embed` isn't given
+/// an explicit `chunk_size`.
+const DEFAULT_EMBED_CHUNK_SIZE: usize = 64;
+/// Default number of embedding chunk requests kept in flight at once.
+const DEFAULT_EMBED_CONCURRENCY: usize = 4;
 
 #[derive(Debug, Error)]
 pub enum RouterError {
@@ -28,19 +47,189 @@ impl From<RouterError> for PyErr {
     }
 }
 
+/// A chat completion request, shared across every `crate::adapters::InferenceBackend`
+/// implementation so they don't each invent their own request shape.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LlmRequest {
+    pub model: String,
+    pub messages: Vec<serde_json::Value>,
+    pub max_tokens: Option<usize>,
+    pub temperature: Option<f64>,
+    pub tools: Option<serde_json::Value>,
+    pub tool_choice: Option<serde_json::Value>,
+}
+
+/// A chat completion result, returned by every `InferenceBackend::call_llm` impl.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LlmResponse {
+    pub content: String,
+    pub model: String,
+    pub finish_reason: Option<String>,
+    pub tool_calls: Vec<serde_json::Value>,
+    pub usage: Option<UsageInfo>,
+}
+
+/// Token accounting for one `call_llm` call.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct UsageInfo {
+    pub prompt_tokens: usize,
+    pub completion_tokens: usize,
+    pub total_tokens: usize,
+}
+
+/// A model known to an `InferenceBackend`, as surfaced by `discover_models`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub id: String,
+    pub name: String,
+    pub backend: String,
+    pub context_length: Option<usize>,
+    pub max_tokens: Option<usize>,
+    pub loaded: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ChatResponse { choices: Vec<ChatChoice>, model: String }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ChatChoice { message: ChatMessage, finish_reason: Option<String> }
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ChatMessage { content: Option<String> }
+struct ChatMessage { content: Option<String>, #[serde(default)] tool_calls: Option<Vec<serde_json::Value>> }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct EmbedResponse { data: Vec<EmbedData> }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct EmbedData { embedding: Vec<f32> }
 
+#[derive(Debug, Clone, Deserialize)]
+struct StreamChunk { choices: Vec<StreamChoice> }
+#[derive(Debug, Clone, Deserialize)]
+struct StreamChoice { delta: StreamDelta }
+#[derive(Debug, Clone, Default, Deserialize)]
+struct StreamDelta { #[serde(default)] content: Option<String> }
+
 struct Backend { base_url: String, api_key: Option<String> }
-struct RouterInner { backends: HashMap<String, Backend>, model_map: HashMap<String, (String, String)> }
+
+/// One `(backend, model_id)` candidate for a model-mapping key, with its
+/// weighted round-robin state.
+struct ModelTarget { backend: String, model_id: String, weight: f64, current_weight: f64 }
+
+/// Consecutive-failure tracking used to temporarily eject an unhealthy backend.
+#[derive(Debug, Clone, Default)]
+struct BackendHealth { consecutive_failures: u32, ejected_until: Option<Instant> }
+
+impl BackendHealth {
+    fn is_ejected(&self) -> bool {
+        self.ejected_until.map(|until| Instant::now() < until).unwrap_or(false)
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.ejected_until = None;
+    }
+
+    fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+            self.ejected_until = Some(Instant::now() + EJECTION_DURATION);
+        }
+    }
+}
+
+struct RouterInner {
+    backends: HashMap<String, Backend>,
+    model_map: HashMap<String, Vec<ModelTarget>>,
+    health: HashMap<String, BackendHealth>,
+}
+
+/// Pick the next healthy, not-yet-tried target using smooth weighted
+/// round-robin, mutating `current_weight` so selection stays balanced
+/// across calls. Falls back to any not-yet-tried target if every
+/// candidate is currently ejected, so a request never hard-fails just
+/// because health tracking ejected every backend.
+fn pick_target(targets: &mut [ModelTarget], health: &HashMap<String, BackendHealth>, tried: &std::collections::HashSet<String>) -> Option<usize> {
+    let healthy = |t: &ModelTarget| !tried.contains(&t.backend) && !health.get(&t.backend).map(BackendHealth::is_ejected).unwrap_or(false);
+    let any_healthy = targets.iter().any(healthy);
+    let eligible = |t: &ModelTarget| !tried.contains(&t.backend) && (any_healthy == healthy(t));
+
+    let mut total = 0.0;
+    let mut best_idx: Option<usize> = None;
+    let mut best_weight = f64::MIN;
+    for (i, t) in targets.iter_mut().enumerate() {
+        if !eligible(t) { continue; }
+        t.current_weight += t.weight;
+        total += t.weight;
+        if t.current_weight > best_weight {
+            best_weight = t.current_weight;
+            best_idx = Some(i);
+        }
+    }
+    if let Some(idx) = best_idx {
+        targets[idx].current_weight -= total;
+    }
+    best_idx
+}
+
+/// Resolve the next `(backend, model_id)` to try for `model_key_or_id`,
+/// skipping backends already in `tried`. Falls back to treating
+/// `model_key_or_id` as a literal model id on the first (arbitrary)
+/// registered backend when there's no mapping for it at all.
+async fn resolve_target(inner_arc: &Arc<TokioMutex<RouterInner>>, model_key_or_id: &str, tried: &std::collections::HashSet<String>) -> Option<(String, String)> {
+    let mut inner = inner_arc.lock().await;
+    let health = inner.health.clone();
+    if let Some(targets) = inner.model_map.get_mut(model_key_or_id) {
+        return pick_target(targets, &health, tried).map(|idx| (targets[idx].backend.clone(), targets[idx].model_id.clone()));
+    }
+    if tried.is_empty() {
+        return inner.backends.keys().next().map(|k| (k.clone(), model_key_or_id.to_string()));
+    }
+    None
+}
+
+/// Record the outcome of a call against `backend_name` for health tracking.
+async fn record_backend_result(inner_arc: &Arc<TokioMutex<RouterInner>>, backend_name: &str, success: bool) {
+    let mut inner = inner_arc.lock().await;
+    let health = inner.health.entry(backend_name.to_string()).or_default();
+    if success { health.record_success(); } else { health.record_failure(); }
+}
+
+/// Embed one chunk of `texts`, failing over across `model_key_or_id`'s
+/// targets with exponential backoff the same way `call_llm` does.
+async fn embed_with_failover(inner_arc: Arc<TokioMutex<RouterInner>>, client: Client, model_key_or_id: String, texts: Vec<String>) -> Result<Vec<Vec<f32>>, RouterError> {
+    let mut tried = std::collections::HashSet::new();
+    let mut last_err = RouterError::BackendNotFound(model_key_or_id.clone());
+    let mut attempt: u32 = 0;
+    while let Some((backend_name, model_id)) = resolve_target(&inner_arc, &model_key_or_id, &tried).await {
+        tried.insert(backend_name.clone());
+        if attempt > 0 {
+            tokio::time::sleep(Duration::from_millis(BASE_BACKOFF_MS * 2u64.pow(attempt - 1))).await;
+        }
+
+        let outcome: Result<EmbedResponse, RouterError> = async {
+            let inner = inner_arc.lock().await;
+            let backend = inner.backends.get(&backend_name).ok_or_else(|| RouterError::BackendNotFound(backend_name.clone()))?;
+            let url = format!("{}/v1/embeddings", backend.base_url.trim_end_matches('/'));
+            let body = serde_json::json!({"model": model_id, "input": texts});
+            let mut req = client.post(&url).json(&body);
+            if let Some(ref key) = backend.api_key { req = req.header("Authorization", format!("Bearer {}", key)); }
+            drop(inner);
+            let response = req.send().await?;
+            if !response.status().is_success() { return Err(RouterError::ApiError(format!("HTTP {}", response.status()))); }
+            Ok(response.json::<EmbedResponse>().await?)
+        }.await;
+
+        match outcome {
+            Ok(embed) => {
+                record_backend_result(&inner_arc, &backend_name, true).await;
+                return Ok(embed.data.into_iter().map(|d| d.embedding).collect());
+            }
+            Err(e) => {
+                record_backend_result(&inner_arc, &backend_name, false).await;
+                last_err = e;
+                attempt += 1;
+            }
+        }
+    }
+    Err(last_err)
+}
 
 #[pyclass]
 pub struct InferenceRouter {
@@ -53,7 +242,7 @@ impl InferenceRouter {
     #[new]
     fn new() -> Self {
         Self {
-            inner: Arc::new(TokioMutex::new(RouterInner { backends: HashMap::new(), model_map: HashMap::new() })),
+            inner: Arc::new(TokioMutex::new(RouterInner { backends: HashMap::new(), model_map: HashMap::new(), health: HashMap::new() })),
             http_client: Client::builder().pool_max_idle_per_host(10).timeout(std::time::Duration::from_secs(300)).build().unwrap(),
         }
     }
@@ -65,25 +254,96 @@ impl InferenceRouter {
         });
     }
 
-    fn add_model_mapping(&self, key: String, backend_name: String, model_id: String) {
+    /// Map `key` to an ordered list of `(backend, model_id, weight)` targets.
+    /// `call_llm`/`embed` select among them with weighted round-robin and
+    /// fail over to the next target on error. Weight defaults to `1.0`.
+    fn add_model_mapping(&self, key: String, targets: Vec<(String, String, Option<f64>)>) {
         pyo3_async_runtimes::tokio::get_runtime().block_on(async {
             let mut inner = self.inner.lock().await;
-            inner.model_map.insert(key, (backend_name, model_id));
+            let targets = targets
+                .into_iter()
+                .map(|(backend, model_id, weight)| ModelTarget { backend, model_id, weight: weight.unwrap_or(1.0), current_weight: 0.0 })
+                .collect();
+            inner.model_map.insert(key, targets);
         });
     }
 
-    fn call_llm<'py>(&self, py: Python<'py>, model_key_or_id: String, messages: Vec<HashMap<String, String>>, max_tokens: Option<usize>, temperature: Option<f64>) -> PyResult<Bound<'py, PyAny>> {
+    #[pyo3(signature = (model_key_or_id, messages, max_tokens=None, temperature=None, tools=None, tool_choice=None))]
+    fn call_llm<'py>(&self, py: Python<'py>, model_key_or_id: String, messages: Vec<HashMap<String, String>>, max_tokens: Option<usize>, temperature: Option<f64>, tools: Option<String>, tool_choice: Option<String>) -> PyResult<Bound<'py, PyAny>> {
         let inner_arc = self.inner.clone();
         let client = self.http_client.clone();
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let msgs: Vec<serde_json::Value> = messages.iter().map(|m| serde_json::json!({"role": m.get("role").unwrap_or(&"user".to_string()), "content": m.get("content").unwrap_or(&String::new())})).collect();
+            let mut tried = std::collections::HashSet::new();
+            let mut last_err = RouterError::BackendNotFound(model_key_or_id.clone());
+            let mut attempt: u32 = 0;
+            while let Some((backend_name, model_id)) = resolve_target(&inner_arc, &model_key_or_id, &tried).await {
+                tried.insert(backend_name.clone());
+                if attempt > 0 {
+                    tokio::time::sleep(Duration::from_millis(BASE_BACKOFF_MS * 2u64.pow(attempt - 1))).await;
+                }
+
+                let outcome: Result<ChatResponse, RouterError> = async {
+                    let inner = inner_arc.lock().await;
+                    let backend = inner.backends.get(&backend_name).ok_or_else(|| RouterError::BackendNotFound(backend_name.clone()))?;
+                    let url = format!("{}/v1/chat/completions", backend.base_url.trim_end_matches('/'));
+                    let mut body = serde_json::json!({"model": model_id, "messages": msgs, "stream": false});
+                    if let Some(mt) = max_tokens { body["max_tokens"] = serde_json::json!(mt); }
+                    if let Some(t) = temperature { body["temperature"] = serde_json::json!(t); }
+                    if let Some(ref tools_json) = tools {
+                        body["tools"] = serde_json::from_str::<serde_json::Value>(tools_json)?;
+                    }
+                    if let Some(ref choice) = tool_choice {
+                        body["tool_choice"] = serde_json::from_str(choice).unwrap_or_else(|_| serde_json::json!(choice));
+                    }
+                    let mut req = client.post(&url).json(&body);
+                    if let Some(ref key) = backend.api_key { req = req.header("Authorization", format!("Bearer {}", key)); }
+                    drop(inner);
+                    let response = req.send().await?;
+                    if !response.status().is_success() { return Err(RouterError::ApiError(format!("HTTP {}", response.status()))); }
+                    Ok(response.json::<ChatResponse>().await?)
+                }.await;
+
+                match outcome {
+                    Ok(chat) => {
+                        record_backend_result(&inner_arc, &backend_name, true).await;
+                        let choice = chat.choices.first();
+                        let content = choice.and_then(|c| c.message.content.clone()).unwrap_or_default();
+                        let tool_calls = choice.and_then(|c| c.message.tool_calls.clone()).unwrap_or_default();
+                        let mut result = HashMap::new();
+                        result.insert("content".to_string(), content);
+                        result.insert("model".to_string(), chat.model);
+                        result.insert("finish_reason".to_string(), choice.and_then(|c| c.finish_reason.clone()).unwrap_or_default());
+                        result.insert("tool_calls".to_string(), serde_json::to_string(&tool_calls)?);
+                        return Ok(result);
+                    }
+                    Err(e) => {
+                        record_backend_result(&inner_arc, &backend_name, false).await;
+                        last_err = e;
+                        attempt += 1;
+                    }
+                }
+            }
+            Err(last_err.into())
+        })
+    }
+
+    /// Stream a chat completion, invoking `on_token(chunk: str)` once per delta.
+    ///
+    /// Resolves the backend/model the same way `call_llm` does, so streaming
+    /// works identically across every registered OpenAI-compatible backend.
+    fn call_llm_stream<'py>(&self, py: Python<'py>, model_key_or_id: String, messages: Vec<HashMap<String, String>>, on_token: Py<PyAny>, max_tokens: Option<usize>, temperature: Option<f64>) -> PyResult<Bound<'py, PyAny>> {
+        let inner_arc = self.inner.clone();
+        let client = self.http_client.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let (backend_name, model_id) = resolve_target(&inner_arc, &model_key_or_id, &std::collections::HashSet::new())
+                .await
+                .ok_or_else(|| RouterError::BackendNotFound(model_key_or_id.clone()))?;
             let inner = inner_arc.lock().await;
-            let (backend_name, model_id) = inner.model_map.get(&model_key_or_id).cloned().unwrap_or_else(|| {
-                inner.backends.keys().next().map(|k| (k.clone(), model_key_or_id.clone())).unwrap_or_default()
-            });
             let backend = inner.backends.get(&backend_name).ok_or_else(|| RouterError::BackendNotFound(backend_name.clone()))?;
             let url = format!("{}/v1/chat/completions", backend.base_url.trim_end_matches('/'));
             let msgs: Vec<serde_json::Value> = messages.iter().map(|m| serde_json::json!({"role": m.get("role").unwrap_or(&"user".to_string()), "content": m.get("content").unwrap_or(&String::new())})).collect();
-            let mut body = serde_json::json!({"model": model_id, "messages": msgs, "stream": false});
+            let mut body = serde_json::json!({"model": model_id, "messages": msgs, "stream": true});
             if let Some(mt) = max_tokens { body["max_tokens"] = serde_json::json!(mt); }
             if let Some(t) = temperature { body["temperature"] = serde_json::json!(t); }
             let mut req = client.post(&url).json(&body);
@@ -91,35 +351,52 @@ impl InferenceRouter {
             drop(inner);
             let response = req.send().await?;
             if !response.status().is_success() { return Err(RouterError::ApiError(format!("HTTP {}", response.status())).into()); }
-            let chat: ChatResponse = response.json().await?;
-            let content = chat.choices.first().and_then(|c| c.message.content.clone()).unwrap_or_default();
-            let mut result = HashMap::new();
-            result.insert("content".to_string(), content);
-            result.insert("model".to_string(), chat.model);
-            result.insert("finish_reason".to_string(), chat.choices.first().and_then(|c| c.finish_reason.clone()).unwrap_or_default());
-            Ok(result)
+
+            let mut stream = response.bytes_stream();
+            let mut buffer = String::new();
+            let mut full_content = String::new();
+            while let Some(chunk_result) = stream.next().await {
+                let chunk = chunk_result?;
+                buffer.push_str(&String::from_utf8_lossy(&chunk));
+                while let Some(newline_pos) = buffer.find('\n') {
+                    let line = buffer[..newline_pos].trim().to_string();
+                    buffer = buffer[newline_pos + 1..].to_string();
+                    let Some(data) = line.strip_prefix("data: ") else { continue };
+                    if data == "[DONE]" { continue; }
+                    if let Ok(parsed) = serde_json::from_str::<StreamChunk>(data) {
+                        for choice in parsed.choices {
+                            if let Some(content) = choice.delta.content {
+                                full_content.push_str(&content);
+                                Python::with_gil(|py| on_token.call1(py, (content,)))?;
+                            }
+                        }
+                    }
+                }
+            }
+            Ok(full_content)
         })
     }
 
-    fn embed<'py>(&self, py: Python<'py>, model_key_or_id: String, texts: Vec<String>) -> PyResult<Bound<'py, PyAny>> {
+    /// Embed `texts`, split into `chunk_size`-sized requests (default
+    /// `DEFAULT_EMBED_CHUNK_SIZE`) dispatched with up to `max_concurrency`
+    /// (default `DEFAULT_EMBED_CONCURRENCY`) in flight at once, so large
+    /// batches neither stall on one giant request nor risk a backend's
+    /// per-request size limit. Results are reassembled in input order; a
+    /// failure in any chunk aborts the rest and surfaces as the first error.
+    #[pyo3(signature = (model_key_or_id, texts, chunk_size=None, max_concurrency=None))]
+    fn embed<'py>(&self, py: Python<'py>, model_key_or_id: String, texts: Vec<String>, chunk_size: Option<usize>, max_concurrency: Option<usize>) -> PyResult<Bound<'py, PyAny>> {
         let inner_arc = self.inner.clone();
         let client = self.http_client.clone();
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let inner = inner_arc.lock().await;
-            let (backend_name, model_id) = inner.model_map.get(&model_key_or_id).cloned().unwrap_or_else(|| {
-                inner.backends.keys().next().map(|k| (k.clone(), model_key_or_id.clone())).unwrap_or_default()
-            });
-            let backend = inner.backends.get(&backend_name).ok_or_else(|| RouterError::BackendNotFound(backend_name.clone()))?;
-            let url = format!("{}/v1/embeddings", backend.base_url.trim_end_matches('/'));
-            let body = serde_json::json!({"model": model_id, "input": texts});
-            let mut req = client.post(&url).json(&body);
-            if let Some(ref key) = backend.api_key { req = req.header("Authorization", format!("Bearer {}", key)); }
-            drop(inner);
-            let response = req.send().await?;
-            if !response.status().is_success() { return Err(RouterError::ApiError(format!("HTTP {}", response.status())).into()); }
-            let embed: EmbedResponse = response.json().await?;
-            let embeddings: Vec<Vec<f32>> = embed.data.into_iter().map(|d| d.embedding).collect();
-            Ok(embeddings)
+            let chunk_size = chunk_size.unwrap_or(DEFAULT_EMBED_CHUNK_SIZE).max(1);
+            let max_concurrency = max_concurrency.unwrap_or(DEFAULT_EMBED_CONCURRENCY).max(1);
+
+            let chunked: Vec<Vec<Vec<f32>>> = stream::iter(texts.chunks(chunk_size).map(|c| c.to_vec()))
+                .map(|chunk| embed_with_failover(inner_arc.clone(), client.clone(), model_key_or_id.clone(), chunk))
+                .buffered(max_concurrency)
+                .try_collect()
+                .await?;
+            Ok(chunked.into_iter().flatten().collect::<Vec<Vec<f32>>>())
         })
     }
 
@@ -130,10 +407,178 @@ impl InferenceRouter {
         })
     }
 
-    fn get_model_mapping(&self) -> HashMap<String, (String, String)> {
+    fn get_model_mapping(&self) -> HashMap<String, Vec<(String, String, f64)>> {
+        pyo3_async_runtimes::tokio::get_runtime().block_on(async {
+            let inner = self.inner.lock().await;
+            inner
+                .model_map
+                .iter()
+                .map(|(key, targets)| (key.clone(), targets.iter().map(|t| (t.backend.clone(), t.model_id.clone(), t.weight)).collect()))
+                .collect()
+        })
+    }
+
+    /// Per-backend health: `(consecutive_failures, is_ejected)`.
+    fn get_backend_health(&self) -> HashMap<String, (u32, bool)> {
         pyo3_async_runtimes::tokio::get_runtime().block_on(async {
             let inner = self.inner.lock().await;
-            inner.model_map.clone()
+            inner
+                .health
+                .iter()
+                .map(|(name, h)| (name.clone(), (h.consecutive_failures, h.is_ejected())))
+                .collect()
         })
     }
+
+    /// Serve this router's backends/model map as an OpenAI-compatible HTTP
+    /// gateway, so non-Python clients can point an OpenAI base URL at
+    /// `http://{host}:{port}` and transparently get failover/load-balancing.
+    fn serve(&self, host: String, port: u16) -> PyResult<ServerHandle> {
+        let addr: std::net::SocketAddr = format!("{host}:{port}")
+            .parse()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("invalid address {host}:{port}: {e}")))?;
+        let state = ProxyState { inner: self.inner.clone(), client: self.http_client.clone() };
+        let app = Router::new()
+            .route("/v1/chat/completions", post(proxy_chat_completions))
+            .route("/v1/embeddings", post(proxy_embeddings))
+            .with_state(state);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+
+        // Bind before returning a handle: binding inside the spawned task
+        // let `serve()` hand back a live-looking `ServerHandle` for a server
+        // that never started, with the failure only ever reaching an
+        // eprintln! in a detached task. Binding here surfaces it as a real
+        // `Err` to the caller instead.
+        let listener = pyo3_async_runtimes::tokio::get_runtime()
+            .block_on(tokio::net::TcpListener::bind(addr))
+            .map_err(|e| pyo3::exceptions::PyOSError::new_err(format!("failed to bind {addr}: {e}")))?;
+
+        pyo3_async_runtimes::tokio::get_runtime().spawn(async move {
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async { let _ = shutdown_rx.await; })
+                .await;
+        });
+
+        Ok(ServerHandle { shutdown_tx: Some(shutdown_tx) })
+    }
+}
+
+/// Shared state for the OpenAI-compatible proxy routes.
+#[derive(Clone)]
+struct ProxyState {
+    inner: Arc<TokioMutex<RouterInner>>,
+    client: Client,
+}
+
+/// Handle to a running `InferenceRouter::serve` gateway.
+#[pyclass]
+pub struct ServerHandle {
+    shutdown_tx: Option<oneshot::Sender<()>>,
+}
+
+#[pymethods]
+impl ServerHandle {
+    /// Gracefully stop the proxy server. Safe to call more than once.
+    fn shutdown(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+/// Forward an OpenAI-style `/v1/chat/completions` request, resolving `model`
+/// through the router's model map and retrying the next target on failure.
+/// Honors `stream: true` by relaying the upstream SSE body unchanged.
+async fn proxy_chat_completions(State(state): State<ProxyState>, Json(payload): Json<serde_json::Value>) -> Response {
+    let model_key = payload.get("model").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let is_stream = payload.get("stream").and_then(|v| v.as_bool()).unwrap_or(false);
+    let mut tried = std::collections::HashSet::new();
+
+    loop {
+        let Some((backend_name, model_id)) = resolve_target(&state.inner, &model_key, &tried).await else {
+            return (StatusCode::BAD_GATEWAY, "no healthy backend available for model").into_response();
+        };
+        tried.insert(backend_name.clone());
+
+        let Some((base_url, api_key)) = (async {
+            let inner = state.inner.lock().await;
+            inner.backends.get(&backend_name).map(|b| (b.base_url.clone(), b.api_key.clone()))
+        })
+        .await
+        else {
+            continue;
+        };
+
+        let mut body = payload.clone();
+        body["model"] = serde_json::json!(model_id);
+        let url = format!("{}/v1/chat/completions", base_url.trim_end_matches('/'));
+        let mut req = state.client.post(&url).json(&body);
+        if let Some(ref key) = api_key { req = req.header("Authorization", format!("Bearer {}", key)); }
+
+        let response = match req.send().await {
+            Ok(r) if r.status().is_success() => r,
+            _ => {
+                record_backend_result(&state.inner, &backend_name, false).await;
+                continue;
+            }
+        };
+        record_backend_result(&state.inner, &backend_name, true).await;
+
+        if is_stream {
+            let stream = response.bytes_stream();
+            let body = axum::body::Body::from_stream(stream);
+            return Response::builder()
+                .header("content-type", "text/event-stream")
+                .body(body)
+                .unwrap_or_default();
+        }
+
+        return match response.bytes().await {
+            Ok(bytes) => ([("content-type", "application/json")], bytes).into_response(),
+            Err(e) => (StatusCode::BAD_GATEWAY, format!("upstream read failed: {e}")).into_response(),
+        };
+    }
+}
+
+/// Forward an OpenAI-style `/v1/embeddings` request, resolving `model`
+/// through the router's model map and retrying the next target on failure.
+async fn proxy_embeddings(State(state): State<ProxyState>, Json(payload): Json<serde_json::Value>) -> Response {
+    let model_key = payload.get("model").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let mut tried = std::collections::HashSet::new();
+
+    loop {
+        let Some((backend_name, model_id)) = resolve_target(&state.inner, &model_key, &tried).await else {
+            return (StatusCode::BAD_GATEWAY, "no healthy backend available for model").into_response();
+        };
+        tried.insert(backend_name.clone());
+
+        let Some((base_url, api_key)) = (async {
+            let inner = state.inner.lock().await;
+            inner.backends.get(&backend_name).map(|b| (b.base_url.clone(), b.api_key.clone()))
+        })
+        .await
+        else {
+            continue;
+        };
+
+        let mut body = payload.clone();
+        body["model"] = serde_json::json!(model_id);
+        let url = format!("{}/v1/embeddings", base_url.trim_end_matches('/'));
+        let mut req = state.client.post(&url).json(&body);
+        if let Some(ref key) = api_key { req = req.header("Authorization", format!("Bearer {}", key)); }
+
+        let response = match req.send().await {
+            Ok(r) if r.status().is_success() => r,
+            _ => {
+                record_backend_result(&state.inner, &backend_name, false).await;
+                continue;
+            }
+        };
+        record_backend_result(&state.inner, &backend_name, true).await;
+
+        return match response.bytes().await {
+            Ok(bytes) => ([("content-type", "application/json")], bytes).into_response(),
+            Err(e) => (StatusCode::BAD_GATEWAY, format!("upstream read failed: {e}")).into_response(),
+        };
+    }
 }
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use super::{MemoryBackend, MemoryBackendError, MetadataFilter, MAX_MEMORY_ENTRIES};
+use crate::vector::MemoryEntry;
+
+/// Fraction of tombstoned (evicted) lines in the persistence file, relative
+/// to total lines written, above which `store` triggers a compaction pass.
+const COMPACTION_DEAD_RATIO: f64 = 0.3;
+
+/// Marks the oldest still-live entry read so far as evicted, in FIFO order —
+/// appended instead of rewriting the file when `store`'s `MAX_MEMORY_ENTRIES`
+/// cap evicts the oldest entry. A tombstone doesn't name an entry; on replay
+/// it just drops the front of the in-order queue being rebuilt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Tombstone {
+    tombstone: bool,
+}
+
+/// Cached live/dead line counts so `store` can decide whether to evict or
+/// compact without rescanning the file on every call.
+struct FileStoreState {
+    live_count: usize,
+    dead_count: usize,
+}
+
+/// `MemoryBackend` over an append-only JSONL file: one `MemoryEntry` per
+/// line, with `Tombstone` lines marking FIFO evictions so a steady-state
+/// `store` never has to rewrite the whole file. `store` rewrites the file
+/// from scratch (dropping tombstoned lines) once dead lines make up more
+/// than `COMPACTION_DEAD_RATIO` of the total.
+pub struct FileStore {
+    path: PathBuf,
+    state: RwLock<Option<FileStoreState>>,
+}
+
+impl FileStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, state: RwLock::new(None) }
+    }
+
+    /// Replay the file in order: tombstone lines drop the oldest still-live
+    /// entry read so far, mirroring FIFO eviction exactly. Returns the live
+    /// entries plus how many tombstone lines were seen (the file's current
+    /// dead-line count).
+    fn load_entries(path: &Path) -> Result<(Vec<MemoryEntry>, usize), MemoryBackendError> {
+        if !path.exists() {
+            return Ok((Vec::new(), 0));
+        }
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+        let mut entries: VecDeque<MemoryEntry> = VecDeque::new();
+        let mut dead_count = 0usize;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Ok(tombstone) = serde_json::from_str::<Tombstone>(&line) {
+                if tombstone.tombstone {
+                    entries.pop_front();
+                    dead_count += 1;
+                    continue;
+                }
+            }
+            if let Ok(entry) = serde_json::from_str::<MemoryEntry>(&line) {
+                entries.push_back(entry);
+            }
+        }
+        Ok((entries.into(), dead_count))
+    }
+
+    fn append_entry(path: &Path, entry: &MemoryEntry) -> Result<(), MemoryBackendError> {
+        let file = OpenOptions::new().append(true).create(true).open(path)?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", serde_json::to_string(entry)?)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn append_tombstone(path: &Path) -> Result<(), MemoryBackendError> {
+        let file = OpenOptions::new().append(true).create(true).open(path)?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", serde_json::to_string(&Tombstone { tombstone: true })?)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Rewrite `path` from scratch with only live `entries`, dropping every
+    /// tombstoned line.
+    fn compact_to_disk(path: &Path, entries: &[MemoryEntry]) -> Result<(), MemoryBackendError> {
+        let file = OpenOptions::new().write(true).create(true).truncate(true).open(path)?;
+        let mut writer = BufWriter::new(file);
+        for entry in entries {
+            writeln!(writer, "{}", serde_json::to_string(entry)?)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Live/dead line counts, loading the file on first use and caching the
+    /// result for subsequent calls.
+    fn counts(&self) -> Result<(usize, usize), MemoryBackendError> {
+        if let Some(state) = self.state.read().as_ref() {
+            return Ok((state.live_count, state.dead_count));
+        }
+        let (entries, dead_count) = Self::load_entries(&self.path)?;
+        let live_count = entries.len();
+        *self.state.write() = Some(FileStoreState { live_count, dead_count });
+        Ok((live_count, dead_count))
+    }
+}
+
+#[async_trait]
+impl MemoryBackend for FileStore {
+    async fn load(&self) -> Result<Vec<MemoryEntry>, MemoryBackendError> {
+        let (entries, dead_count) = Self::load_entries(&self.path)?;
+        *self.state.write() = Some(FileStoreState { live_count: entries.len(), dead_count });
+        Ok(entries)
+    }
+
+    async fn store(&self, entry: MemoryEntry) -> Result<(), MemoryBackendError> {
+        let (mut live_count, mut dead_count) = self.counts()?;
+        Self::append_entry(&self.path, &entry)?;
+        live_count += 1;
+        while live_count > MAX_MEMORY_ENTRIES {
+            Self::append_tombstone(&self.path)?;
+            live_count -= 1;
+            dead_count += 1;
+        }
+
+        let total_lines = live_count + dead_count;
+        if total_lines > 0 && dead_count as f64 / total_lines as f64 > COMPACTION_DEAD_RATIO {
+            let (entries, _) = Self::load_entries(&self.path)?;
+            Self::compact_to_disk(&self.path, &entries)?;
+            dead_count = 0;
+        }
+
+        *self.state.write() = Some(FileStoreState { live_count, dead_count });
+        Ok(())
+    }
+
+    async fn search(&self, filter: &MetadataFilter) -> Result<Vec<MemoryEntry>, MemoryBackendError> {
+        let (entries, _) = Self::load_entries(&self.path)?;
+        Ok(entries.into_iter().filter(|e| filter.matches(e)).collect())
+    }
+
+    async fn count(&self) -> Result<usize, MemoryBackendError> {
+        Ok(self.counts()?.0)
+    }
+
+    async fn clear(&self) -> Result<(), MemoryBackendError> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        *self.state.write() = Some(FileStoreState { live_count: 0, dead_count: 0 });
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use parking_lot::RwLock;
+
+use super::{MemoryBackend, MemoryBackendError, MetadataFilter, MAX_MEMORY_ENTRIES};
+use crate::vector::MemoryEntry;
+
+/// Non-durable `MemoryBackend` backed by a `Vec` held in the process's own
+/// memory — no file, no network, gone when the process exits. Mirrors
+/// `FileStore`'s FIFO capacity policy so pointing `VectorMemory` at
+/// `memory://` for tests or ephemeral sessions doesn't change its eviction
+/// behavior, just its durability.
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: RwLock<Vec<MemoryEntry>>,
+}
+
+#[async_trait]
+impl MemoryBackend for MemoryStore {
+    async fn load(&self) -> Result<Vec<MemoryEntry>, MemoryBackendError> {
+        Ok(self.entries.read().clone())
+    }
+
+    async fn store(&self, entry: MemoryEntry) -> Result<(), MemoryBackendError> {
+        let mut entries = self.entries.write();
+        entries.push(entry);
+        while entries.len() > MAX_MEMORY_ENTRIES {
+            entries.remove(0);
+        }
+        Ok(())
+    }
+
+    async fn search(&self, filter: &MetadataFilter) -> Result<Vec<MemoryEntry>, MemoryBackendError> {
+        Ok(self.entries.read().iter().filter(|e| filter.matches(e)).cloned().collect())
+    }
+
+    async fn count(&self) -> Result<usize, MemoryBackendError> {
+        Ok(self.entries.read().len())
+    }
+
+    async fn clear(&self) -> Result<(), MemoryBackendError> {
+        self.entries.write().clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Memory Backends
+//!
+//! Pluggable storage/retrieval for `VectorMemory`'s semantic entries. Vector
+//! math (HNSW, quantization, BM25, temporal filtering) stays in `vector.rs`;
+//! a `MemoryBackend` only owns durability and, where possible, server-side
+//! metadata filtering, so the engine can target an in-process file, a
+//! non-durable in-memory cache, or (eventually) a networked store without
+//! its ranking logic changing.
+
+mod file_store;
+mod memory_store;
+
+pub use file_store::FileStore;
+pub use memory_store::MemoryStore;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::vector::MemoryEntry;
+
+/// Entries above this count are evicted FIFO by every backend below, mirroring
+/// `VectorMemory`'s historical single-process cap.
+pub(crate) const MAX_MEMORY_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Error)]
+pub enum MemoryBackendError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Unsupported backend URI: {0} (expected 'jsonl://<path>', 'memory://', a bare file path, or 'postgres://...')")]
+    UnsupportedUri(String),
+    #[error("Backend not implemented: {0}")]
+    NotImplemented(String),
+}
+
+/// Metadata pushed down to a `MemoryBackend::search` call so backends that
+/// can filter server-side (e.g. a SQL `WHERE` clause) don't have to hand the
+/// full corpus back to the engine just to have most of it thrown away. Every
+/// field defaults to "don't filter on this"; `FileStore` and `MemoryStore`
+/// apply it with a plain scan via `matches`.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataFilter {
+    /// An entry matches if its comma-separated tags include at least one of these.
+    pub tags: Vec<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub source: Option<String>,
+}
+
+impl MetadataFilter {
+    pub fn matches(&self, entry: &MemoryEntry) -> bool {
+        if !self.tags.is_empty() {
+            let entry_tags: std::collections::HashSet<&str> = entry.tags.split(',').map(|t| t.trim()).collect();
+            if !self.tags.iter().any(|t| entry_tags.contains(t.as_str())) {
+                return false;
+            }
+        }
+        if let Some(ref entity_type) = self.entity_type {
+            if entry.entity_type != *entity_type {
+                return false;
+            }
+        }
+        if let Some(ref entity_id) = self.entity_id {
+            if entry.entity_id != *entity_id {
+                return false;
+            }
+        }
+        if let Some(ref source) = self.source {
+            if entry.source != *source {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Storage/retrieval for `MemoryEntry` records, independent of the vector
+/// math `VectorMemory` runs over them.
+#[async_trait]
+pub trait MemoryBackend: Send + Sync {
+    /// Bulk-load every live entry, in insertion order. Used for initial cache
+    /// construction and for the full resync `VectorMemory` runs once evicted
+    /// entries make up more than its index compaction ratio.
+    async fn load(&self) -> Result<Vec<MemoryEntry>, MemoryBackendError>;
+
+    /// Durably persist one new entry, enforcing `MAX_MEMORY_ENTRIES` with
+    /// FIFO eviction if the backend is at capacity.
+    async fn store(&self, entry: MemoryEntry) -> Result<(), MemoryBackendError>;
+
+    /// Entries matching `filter`. Backends that can push the filter down to
+    /// their underlying store should; the default expectation (met by both
+    /// backends in this module) is a full scan through `filter.matches`.
+    async fn search(&self, filter: &MetadataFilter) -> Result<Vec<MemoryEntry>, MemoryBackendError>;
+
+    /// Number of live entries.
+    async fn count(&self) -> Result<usize, MemoryBackendError>;
+
+    /// Durably remove every entry.
+    async fn clear(&self) -> Result<(), MemoryBackendError>;
+}
+
+/// Select a `MemoryBackend` by URI scheme: `jsonl://<path>` or a bare path
+/// (`VectorMemory`'s historical default) for a durable `FileStore`,
+/// `memory://` for a non-durable `MemoryStore`, or `postgres://...` for a
+/// networked store (a recognized scheme, not yet implemented). Also returns
+/// the on-disk path backing the store, if any, so the caller can colocate
+/// sidecar files (e.g. `VectorMemory`'s HNSW graph) next to it.
+pub fn select_backend(uri: &str) -> Result<(Arc<dyn MemoryBackend>, Option<PathBuf>), MemoryBackendError> {
+    if let Some(path) = uri.strip_prefix("jsonl://") {
+        let path = PathBuf::from(path);
+        return Ok((Arc::new(FileStore::new(path.clone())), Some(path)));
+    }
+    if uri == "memory://" || uri == "memory" {
+        return Ok((Arc::new(MemoryStore::default()), None));
+    }
+    if uri.starts_with("postgres://") {
+        return Err(MemoryBackendError::NotImplemented(format!("postgres backend ({uri})")));
+    }
+    if uri.contains("://") {
+        return Err(MemoryBackendError::UnsupportedUri(uri.to_string()));
+    }
+    let path = PathBuf::from(uri);
+    Ok((Arc::new(FileStore::new(path.clone())), Some(path)))
+}
@@ -0,0 +1,91 @@
+//! OpenTelemetry instrumentation for the SQLite-backed subsystems
+//!
+//! Metrics and spans are read from `opentelemetry::global`, which defaults to a
+//! no-op provider until `init_telemetry` installs a real OTLP exporter, so every
+//! call site below is free to record unconditionally.
+
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use pyo3::prelude::*;
+use tracing_subscriber::layer::SubscriberExt;
+
+const OTLP_ENDPOINT_ENV: &str = "MOOSE_OTLP_ENDPOINT";
+const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+static METER: Lazy<Meter> = Lazy::new(|| opentelemetry::global::meter("moose_core"));
+
+static MESSAGES_SENT: Lazy<Counter<u64>> = Lazy::new(|| METER.u64_counter("moose.messages.sent").with_description("Messages sent through the MessageBus").init());
+static INJECTION_WARNINGS: Lazy<Counter<u64>> = Lazy::new(|| METER.u64_counter("moose.injection_warnings").with_description("Prompt injection patterns matched in message content").init());
+static MEMORIES_EVICTED: Lazy<Counter<u64>> = Lazy::new(|| METER.u64_counter("moose.memories.evicted").with_description("Episodic memories evicted for low importance").init());
+static QUERY_LATENCY: Lazy<Histogram<f64>> = Lazy::new(|| METER.f64_histogram("moose.sqlite.query_latency_ms").with_description("SQLite query latency in milliseconds").init());
+static ROWS_RETURNED: Lazy<Histogram<u64>> = Lazy::new(|| METER.u64_histogram("moose.sqlite.rows_returned").with_description("Rows returned by a query").init());
+static SUMMARY_SIZE: Lazy<Histogram<u64>> = Lazy::new(|| METER.u64_histogram("moose.workspace.summary_size_bytes").with_description("Size in bytes of a generated mission summary").init());
+
+/// Installs an OTLP exporter as the global tracer/meter provider, and bridges
+/// `tracing`'s global subscriber to that tracer so `#[tracing::instrument]`
+/// spans (e.g. the `mission_id`/`agent_id`/`memory_type` fields recorded
+/// throughout `workspace.rs`/`episodic.rs`/`messages.rs`) are exported as
+/// OTLP spans rather than just living in `tracing`'s in-process machinery.
+/// The endpoint defaults to `MOOSE_OTLP_ENDPOINT` (or `http://localhost:4317`)
+/// when not given explicitly. Calling this is entirely optional: without it,
+/// every recording function below is a cheap no-op via the default global
+/// providers, and `#[tracing::instrument]` spans simply have no subscriber.
+#[pyfunction]
+#[pyo3(signature = (otlp_endpoint=None))]
+pub fn init_telemetry(otlp_endpoint: Option<String>) -> PyResult<()> {
+    let endpoint = otlp_endpoint
+        .or_else(|| std::env::var(OTLP_ENDPOINT_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_OTLP_ENDPOINT.to_string());
+
+    let tracer_provider = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint).with_timeout(Duration::from_secs(5)))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to install OTLP tracer: {e}")))?;
+    opentelemetry::global::set_tracer_provider(tracer_provider.clone());
+
+    let meter_provider = opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry_sdk::runtime::Tokio)
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(&endpoint).with_timeout(Duration::from_secs(5)))
+        .build()
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to install OTLP meter: {e}")))?;
+    opentelemetry::global::set_meter_provider(meter_provider);
+
+    let tracer = opentelemetry::trace::TracerProvider::tracer(&tracer_provider, "moose_core");
+    let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+    let subscriber = tracing_subscriber::Registry::default().with(otel_layer);
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to install tracing subscriber: {e}")))?;
+
+    Ok(())
+}
+
+pub fn record_message_sent(mission_id: &str) {
+    MESSAGES_SENT.add(1, &[KeyValue::new("mission_id", mission_id.to_string())]);
+}
+
+pub fn record_injection_warning(sender: &str) {
+    INJECTION_WARNINGS.add(1, &[KeyValue::new("sender", sender.to_string())]);
+}
+
+pub fn record_memories_evicted(count: u64, memory_type: &str) {
+    if count > 0 {
+        MEMORIES_EVICTED.add(count, &[KeyValue::new("memory_type", memory_type.to_string())]);
+    }
+}
+
+pub fn record_query_latency(subsystem: &str, elapsed: Duration) {
+    QUERY_LATENCY.record(elapsed.as_secs_f64() * 1000.0, &[KeyValue::new("subsystem", subsystem.to_string())]);
+}
+
+pub fn record_rows_returned(subsystem: &str, rows: usize) {
+    ROWS_RETURNED.record(rows as u64, &[KeyValue::new("subsystem", subsystem.to_string())]);
+}
+
+pub fn record_summary_size(mission_id: &str, bytes: usize) {
+    SUMMARY_SIZE.record(bytes as u64, &[KeyValue::new("mission_id", mission_id.to_string())]);
+}
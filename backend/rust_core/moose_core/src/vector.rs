@@ -1,15 +1,16 @@
 //! Vector Memory Engine
 //!
-//! High-performance semantic memory store with SIMD-accelerated cosine similarity search.
+//! High-performance semantic memory store with an incremental HNSW
+//! approximate-nearest-neighbor index (falling back to an exact cosine scan
+//! for small corpora), BM25 keyword search, optional scalar/binary vector
+//! quantization, and pluggable `MemoryBackend` persistence (an append-only
+//! JSONL file by default).
 
 use std::collections::HashMap;
-use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use chrono::Utc;
-use ndarray::{Array1, Array2};
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 use pyo3::prelude::*;
@@ -20,11 +21,36 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::sync::Mutex as TokioMutex;
 
-const MAX_MEMORY_ENTRIES: usize = 10_000;
+use crate::adapters::{InferenceBackend, LlamaCppBackend, OllamaBackend, OpenAICompatBackend};
+use crate::stores::{self, MemoryBackend, MemoryBackendError, MetadataFilter};
+
 const DEFAULT_MEMORY_PATH: &str = "backend/memory.jsonl";
 static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-zA-Z0-9_\-]+$").unwrap());
 const MAX_TAGS: usize = 20;
 const MAX_TAG_LENGTH: usize = 50;
+/// BM25 term-frequency saturation parameter.
+const BM25_K1: f32 = 1.2;
+/// BM25 document-length normalization parameter.
+const BM25_B: f32 = 0.75;
+/// Reciprocal rank fusion constant (Cormack et al.'s conventional value).
+const RRF_K: f64 = 60.0;
+/// Corpus size at or below which `search` uses the exact cosine matrix scan
+/// instead of the HNSW approximate index; brute force is cheap and exact at
+/// this scale, and avoids HNSW's approximation error for small corpora.
+const HNSW_EXACT_THRESHOLD: usize = 1_000;
+/// Default max neighbors per node per layer (`M` in the HNSW paper).
+const DEFAULT_HNSW_M: usize = 16;
+/// Default candidate list size used while building the graph.
+const DEFAULT_HNSW_EF_CONSTRUCTION: usize = 200;
+/// Default candidate list size used while querying the graph.
+const DEFAULT_HNSW_EF_SEARCH: usize = 64;
+/// Default number of top-ranked quantized candidates re-scored with exact
+/// f32 cosine similarity when `quantization` is `"scalar"` or `"binary"`.
+const DEFAULT_RESCORE_K: usize = 200;
+/// Fraction of `VectorMemoryInner::entries` tombstoned by eviction, above
+/// which `store` pays for a full backend resync and `hnsw` rebuild instead
+/// of just tombstoning the new oldest entry in place.
+const INDEX_COMPACTION_DEAD_RATIO: f64 = 0.3;
 
 #[derive(Debug, Error)]
 pub enum VectorMemoryError {
@@ -40,6 +66,12 @@ pub enum VectorMemoryError {
     InvalidTag(String),
     #[error("Embedding API error: {0}")]
     EmbeddingApiError(String),
+    #[error("Invalid search mode: {0} (expected 'vector', 'keyword', or 'hybrid')")]
+    InvalidMode(String),
+    #[error("Invalid quantization mode: {0} (expected 'none', 'scalar', or 'binary')")]
+    InvalidQuantization(String),
+    #[error("Memory backend error: {0}")]
+    BackendError(#[from] MemoryBackendError),
 }
 
 impl From<VectorMemoryError> for PyErr {
@@ -72,12 +104,331 @@ struct EmbeddingData {
     embedding: Vec<f32>,
 }
 
+/// How `store`/`search`/`embed` turn text into vectors. `Raw` hits
+/// `api_base`'s `/v1/embeddings` directly, the same way this store always
+/// has. `Routed` goes through an `InferenceBackend` adapter, so embedding
+/// picks up that adapter's auth and backend-specific batching quirks. The
+/// adapter is built on this `VectorMemory`'s own HTTP client; it does not
+/// share a client or connection pool with `InferenceRouter`.
+#[derive(Clone)]
+enum Embedder {
+    Raw,
+    Routed(Arc<dyn InferenceBackend>),
+}
+
+impl Default for Embedder {
+    fn default() -> Self {
+        Embedder::Raw
+    }
+}
+
+/// How stored vectors are ranked for the candidate pre-filter pass, applied
+/// regardless of corpus size whenever set to `Scalar`/`Binary`. The winning
+/// candidates are always re-scored with the original f32 vectors before
+/// being returned, so quantization trades a little candidate recall for
+/// scan speed, not result precision. The scalar prefilter scores the int8
+/// codes directly (see `VectorMemory::scalar_code_dot`) rather than
+/// decoding each one back to an f32 vector first, so the scan itself is
+/// cheaper than the exact per-entry dot it's approximating. Note this does
+/// *not* reduce resident memory today: `entries`/`norm_vectors` keep the
+/// full f32 vectors alongside `scalar_codes`/`binary_codes` so the rescore
+/// step and the HNSW graph (maintained regardless of quantization mode)
+/// stay exact, so enabling quantization costs extra memory for the codes
+/// rather than saving any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum QuantizationMode {
+    None,
+    Scalar,
+    Binary,
+}
+
+impl QuantizationMode {
+    fn parse(s: &str) -> Result<Self, VectorMemoryError> {
+        match s {
+            "none" => Ok(Self::None),
+            "scalar" => Ok(Self::Scalar),
+            "binary" => Ok(Self::Binary),
+            other => Err(VectorMemoryError::InvalidQuantization(other.to_string())),
+        }
+    }
+}
+
+/// Int8 scalar quantization codec: per-dimension `(min, scale)` fit from a
+/// corpus, mapping `x` to a code in `-128..=127` via
+/// `round((x - min) / scale) - 128` and back via
+/// `min + (code + 128) * scale`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScalarCodec {
+    mins: Vec<f32>,
+    scales: Vec<f32>,
+}
+
+impl ScalarCodec {
+    fn fit(vectors: &[Vec<f32>]) -> Option<Self> {
+        let dim = vectors.first()?.len();
+        let mut mins = vec![f32::INFINITY; dim];
+        let mut maxs = vec![f32::NEG_INFINITY; dim];
+        for v in vectors {
+            for (j, &x) in v.iter().enumerate() {
+                mins[j] = mins[j].min(x);
+                maxs[j] = maxs[j].max(x);
+            }
+        }
+        let scales = mins.iter().zip(&maxs).map(|(&mn, &mx)| ((mx - mn) / 255.0).max(1e-9)).collect();
+        Some(Self { mins, scales })
+    }
+
+    fn encode(&self, vector: &[f32]) -> Vec<i8> {
+        vector
+            .iter()
+            .zip(&self.mins)
+            .zip(&self.scales)
+            .map(|((&x, &mn), &sc)| (((x - mn) / sc).round().clamp(0.0, 255.0) as i32 - 128) as i8)
+            .collect()
+    }
+
+    fn decode(&self, code: &[i8]) -> Vec<f32> {
+        code.iter()
+            .zip(&self.mins)
+            .zip(&self.scales)
+            .map(|((&c, &mn), &sc)| mn + (c as i32 + 128) as f32 * sc)
+            .collect()
+    }
+}
+
+/// One HNSW graph node: its neighbor links at each layer it participates in,
+/// `neighbors[l]` for `l` in `0..=top_layer`. Vectors live in the parallel
+/// `norm_vectors` cache, not here, so the persisted graph stays small.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HnswNode {
+    neighbors: Vec<Vec<usize>>,
+}
+
+/// Incremental Hierarchical Navigable Small World index over normalized
+/// vectors, following Malkov & Yashunin. Node `i` corresponds to
+/// `norm_vectors[i]` / `entries[i]`; nodes are appended in insertion order
+/// so index positions never need remapping.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HnswIndex {
+    nodes: Vec<HnswNode>,
+    entry_point: Option<usize>,
+}
+
+impl HnswIndex {
+    fn dot(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    }
+
+    /// A pseudo-random value in `(0, 1)`, drawn from `RandomState`'s
+    /// per-construction keys so layer assignment doesn't need a `rand`
+    /// dependency (the same trick `HashMap`'s own seeding relies on).
+    fn random_unit_f64() -> f64 {
+        use std::collections::hash_map::RandomState;
+        use std::hash::{BuildHasher, Hasher};
+        let bits = RandomState::new().build_hasher().finish();
+        ((bits >> 11) as f64 / (1u64 << 53) as f64).clamp(f64::MIN_POSITIVE, 1.0 - f64::EPSILON)
+    }
+
+    /// Geometric-distribution top layer: `floor(-ln(uniform()) * ml)`.
+    fn random_layer(ml: f64) -> usize {
+        (-Self::random_unit_f64().ln() * ml).floor() as usize
+    }
+
+    fn argmax(candidates: &[(usize, f32)]) -> Option<usize> {
+        candidates
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1 .1.partial_cmp(&b.1 .1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+    }
+
+    fn argmin(candidates: &[(usize, f32)]) -> Option<usize> {
+        candidates
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1 .1.partial_cmp(&b.1 .1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+    }
+
+    /// Single-best greedy descent: repeatedly step to the closest neighbor
+    /// of `current` at `layer` until no neighbor improves on it. Used to
+    /// find a good entry point when dropping down a layer.
+    fn greedy_closest(&self, vectors: &[Vec<f32>], query: &[f32], entry: usize, layer: usize) -> usize {
+        let mut current = entry;
+        let mut current_sim = Self::dot(&vectors[current], query);
+        loop {
+            let mut improved = false;
+            if let Some(neighbors) = self.nodes[current].neighbors.get(layer) {
+                for &n in neighbors {
+                    let sim = Self::dot(&vectors[n], query);
+                    if sim > current_sim {
+                        current = n;
+                        current_sim = sim;
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// `ef`-bounded best-first beam search at `layer`, returning up to `ef`
+    /// candidates ordered by descending cosine similarity to `query`.
+    fn search_layer(&self, vectors: &[Vec<f32>], query: &[f32], entry_points: &[usize], layer: usize, ef: usize) -> Vec<(usize, f32)> {
+        let mut visited: std::collections::HashSet<usize> = entry_points.iter().copied().collect();
+        let mut candidates: Vec<(usize, f32)> = entry_points.iter().map(|&ep| (ep, Self::dot(&vectors[ep], query))).collect();
+        let mut results: Vec<(usize, f32)> = candidates.clone();
+
+        while let Some(pos) = Self::argmax(&candidates) {
+            let (current, current_sim) = candidates.remove(pos);
+            if results.len() >= ef {
+                let worst = results[Self::argmin(&results).unwrap()].1;
+                if current_sim < worst {
+                    break;
+                }
+            }
+            let Some(neighbors) = self.nodes[current].neighbors.get(layer) else { continue };
+            for &n in neighbors {
+                if !visited.insert(n) {
+                    continue;
+                }
+                let sim = Self::dot(&vectors[n], query);
+                if results.len() < ef {
+                    candidates.push((n, sim));
+                    results.push((n, sim));
+                } else {
+                    let worst_pos = Self::argmin(&results).unwrap();
+                    if sim > results[worst_pos].1 {
+                        results.remove(worst_pos);
+                        candidates.push((n, sim));
+                        results.push((n, sim));
+                    }
+                }
+            }
+        }
+
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results
+    }
+
+    /// Select up to `m` neighbors for `query` from `candidates`, preferring
+    /// a diverse spread of directions over the closest cluster: a candidate
+    /// is skipped if it's already closer to a selected neighbor than it is
+    /// to `query` (Malkov & Yashunin's neighbor-selection heuristic), and
+    /// skipped candidates only fill slots left over once that pass is done.
+    fn select_neighbors(vectors: &[Vec<f32>], query: &[f32], mut candidates: Vec<(usize, f32)>, m: usize) -> Vec<usize> {
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        let mut selected: Vec<usize> = Vec::with_capacity(m);
+        let mut leftover: Vec<usize> = Vec::new();
+        for (cand, cand_sim) in candidates {
+            if selected.len() >= m {
+                break;
+            }
+            let dominated = selected.iter().any(|&s| Self::dot(&vectors[s], &vectors[cand]) >= cand_sim);
+            if dominated {
+                leftover.push(cand);
+            } else {
+                selected.push(cand);
+            }
+        }
+        for cand in leftover {
+            if selected.len() >= m {
+                break;
+            }
+            selected.push(cand);
+        }
+        selected
+    }
+
+    /// Insert `vectors.last()` (already normalized) into the graph.
+    fn insert(&mut self, vectors: &[Vec<f32>], m: usize, ef_construction: usize, ml: f64) {
+        let new_idx = vectors.len() - 1;
+        let layer = Self::random_layer(ml);
+        self.nodes.push(HnswNode { neighbors: vec![Vec::new(); layer + 1] });
+
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(new_idx);
+            return;
+        };
+
+        let entry_layer = self.nodes[entry].neighbors.len() - 1;
+        let mut current = entry;
+        for l in (layer + 1..=entry_layer).rev() {
+            current = self.greedy_closest(vectors, &vectors[new_idx], current, l);
+        }
+
+        for l in (0..=layer.min(entry_layer)).rev() {
+            let candidates = self.search_layer(vectors, &vectors[new_idx], &[current], l, ef_construction);
+            if let Some(&(best, _)) = candidates.first() {
+                current = best;
+            }
+            let neighbors = Self::select_neighbors(vectors, &vectors[new_idx], candidates, m);
+            self.nodes[new_idx].neighbors[l] = neighbors.clone();
+
+            for &n in &neighbors {
+                if !self.nodes[n].neighbors[l].contains(&new_idx) {
+                    self.nodes[n].neighbors[l].push(new_idx);
+                }
+                if self.nodes[n].neighbors[l].len() > m {
+                    let back_candidates: Vec<(usize, f32)> = self.nodes[n].neighbors[l]
+                        .iter()
+                        .map(|&c| (c, Self::dot(&vectors[c], &vectors[n])))
+                        .collect();
+                    self.nodes[n].neighbors[l] = Self::select_neighbors(vectors, &vectors[n], back_candidates, m);
+                }
+            }
+        }
+
+        if layer > entry_layer {
+            self.entry_point = Some(new_idx);
+        }
+    }
+
+    /// Approximate top-`top_k` cosine neighbors of `query`: greedy descent
+    /// from the entry point down to layer 1, then an `ef_search` beam at
+    /// layer 0.
+    fn search(&self, vectors: &[Vec<f32>], query: &[f32], top_k: usize, ef_search: usize) -> Vec<(usize, f32)> {
+        let Some(entry) = self.entry_point else { return Vec::new() };
+        let top_layer = self.nodes[entry].neighbors.len() - 1;
+        let mut current = entry;
+        for l in (1..=top_layer).rev() {
+            current = self.greedy_closest(vectors, query, current, l);
+        }
+        let mut results = self.search_layer(vectors, query, &[current], 0, ef_search.max(top_k));
+        results.truncate(top_k);
+        results
+    }
+}
+
 struct VectorMemoryInner {
     entries: Vec<MemoryEntry>,
-    vectors: Option<Array2<f32>>,
+    norm_vectors: Vec<Vec<f32>>,
+    hnsw: HnswIndex,
+    /// Count of now-evicted entries still sitting at the front of `entries`/
+    /// `norm_vectors`/the quantized code vectors. `store` tombstones the
+    /// oldest live entry in place (bumping this) instead of resyncing from
+    /// the backend and rebuilding `hnsw` on every eviction, since HNSW node
+    /// indices can't be shifted without a full rebuild. Search excludes
+    /// indices below this count from its results; a full resync-and-rebuild
+    /// only happens once tombstones make up more than
+    /// `INDEX_COMPACTION_DEAD_RATIO` of `entries`, amortizing that cost
+    /// across many stores the same way `FileStore` amortizes its own
+    /// tombstone compaction.
+    dead_head: usize,
     api_base: Option<String>,
     embed_model: Option<String>,
-    persistence_path: PathBuf,
+    embedder: Embedder,
+    quantization: QuantizationMode,
+    scalar_codec: Option<ScalarCodec>,
+    scalar_codes: Vec<Vec<i8>>,
+    binary_codes: Vec<Vec<u64>>,
+    backend: Arc<dyn MemoryBackend>,
+    /// Path of the on-disk HNSW sidecar, if `backend` is file-backed; `None`
+    /// for backends with no filesystem presence (e.g. `memory://`), in which
+    /// case the graph is simply rebuilt from `entries` on every process start.
+    sidecar_path: Option<PathBuf>,
 }
 
 #[pyclass]
@@ -85,6 +436,10 @@ pub struct VectorMemory {
     inner: Arc<RwLock<VectorMemoryInner>>,
     http_client: Client,
     async_lock: Arc<TokioMutex<()>>,
+    hnsw_m: usize,
+    hnsw_ef_construction: usize,
+    hnsw_ef_search: usize,
+    rescore_k: usize,
 }
 
 impl VectorMemory {
@@ -104,62 +459,208 @@ impl VectorMemory {
         Ok(())
     }
 
-    fn load_from_disk(path: &PathBuf) -> Result<Vec<MemoryEntry>, VectorMemoryError> {
-        if !path.exists() {
-            return Ok(Vec::new());
+    fn normalize(vector: &[f32]) -> Vec<f32> {
+        let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 { vector.iter().map(|x| x / norm).collect() } else { vector.to_vec() }
+    }
+
+    fn vec_dot(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+    }
+
+    /// Pack each component's sign into a bit (1 = non-negative), LSB-first
+    /// within each `u64` word.
+    fn binary_encode(vector: &[f32]) -> Vec<u64> {
+        let mut words = vec![0u64; vector.len().div_ceil(64)];
+        for (i, &x) in vector.iter().enumerate() {
+            if x >= 0.0 {
+                words[i / 64] |= 1 << (i % 64);
+            }
         }
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let mut entries = Vec::new();
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
-                continue;
+        words
+    }
+
+    fn hamming_distance(a: &[u64], b: &[u64]) -> u32 {
+        a.iter().zip(b.iter()).map(|(x, y)| (x ^ y).count_ones()).sum()
+    }
+
+    /// Approximate dot product of a code against a query, without decoding
+    /// the code back to an f32 vector first. `ScalarCodec::decode` is the
+    /// affine map `value = min + (code + 128) * scale`, which is linear in
+    /// `code`, so `dot(query, decode(code))` distributes into a per-query
+    /// constant (`base`, folded in by the caller) plus this single pass
+    /// over the raw i8 codes weighted by `query_scaled[j] = query[j] *
+    /// scale[j]` (also precomputed once per query by the caller). That
+    /// keeps the scalar prefilter a single narrow (1-byte-per-dim) scan
+    /// instead of materializing a full f32 vector per entry.
+    fn scalar_code_dot(query_scaled: &[f32], code: &[i8]) -> f32 {
+        query_scaled.iter().zip(code).map(|(&qs, &c)| qs * c as f32).sum()
+    }
+
+    /// Build (or rebuild) `scalar_codes`/`binary_codes` for every entry
+    /// under `mode`, fitting a fresh `ScalarCodec` from the current vectors
+    /// when in scalar mode. Only run at load time, on `store`, and on
+    /// eviction resync — a deterministic refit from the same vectors always
+    /// reproduces the same codec, so there's no need to persist it.
+    fn build_quantization(entries: &[MemoryEntry], mode: QuantizationMode) -> (Option<ScalarCodec>, Vec<Vec<i8>>, Vec<Vec<u64>>) {
+        match mode {
+            QuantizationMode::None => (None, Vec::new(), Vec::new()),
+            QuantizationMode::Scalar => {
+                let vectors: Vec<Vec<f32>> = entries.iter().map(|e| e.vector.clone()).collect();
+                let codec = ScalarCodec::fit(&vectors);
+                let codes = match &codec {
+                    Some(c) => vectors.iter().map(|v| c.encode(v)).collect(),
+                    None => Vec::new(),
+                };
+                (codec, codes, Vec::new())
             }
-            if let Ok(entry) = serde_json::from_str::<MemoryEntry>(&line) {
-                entries.push(entry);
+            QuantizationMode::Binary => {
+                let codes = entries.iter().map(|e| Self::binary_encode(&e.vector)).collect();
+                (None, Vec::new(), codes)
             }
         }
-        Ok(entries)
     }
 
-    fn save_to_disk(path: &PathBuf, entries: &[MemoryEntry]) -> Result<(), VectorMemoryError> {
-        let file = OpenOptions::new().write(true).create(true).truncate(true).open(path)?;
-        let mut writer = BufWriter::new(file);
-        for entry in entries {
-            writeln!(writer, "{}", serde_json::to_string(entry)?)?;
+    /// Path of the HNSW graph sidecar file that's persisted next to a
+    /// file-backed store's JSONL.
+    fn hnsw_path(path: &PathBuf) -> PathBuf {
+        let mut s = path.as_os_str().to_owned();
+        s.push(".hnsw.json");
+        PathBuf::from(s)
+    }
+
+    /// Load the persisted graph if `sidecar` exists and its node count
+    /// matches `entries.len()`; otherwise (including when there's no
+    /// sidecar, e.g. a non-file-backed store) rebuild it from scratch by
+    /// re-inserting every entry's normalized vector in order.
+    fn load_or_rebuild_hnsw(sidecar: Option<&PathBuf>, entries: &[MemoryEntry], norm_vectors: &[Vec<f32>], m: usize, ef_construction: usize) -> HnswIndex {
+        if let Some(sidecar) = sidecar {
+            if let Ok(data) = std::fs::read_to_string(sidecar) {
+                if let Ok(index) = serde_json::from_str::<HnswIndex>(&data) {
+                    if index.nodes.len() == entries.len() {
+                        return index;
+                    }
+                }
+            }
         }
-        writer.flush()?;
+        let ml = 1.0 / (m as f64).ln();
+        let mut index = HnswIndex::default();
+        for i in 0..norm_vectors.len() {
+            index.insert(&norm_vectors[..=i], m, ef_construction, ml);
+        }
+        index
+    }
+
+    fn save_hnsw(path: &PathBuf, index: &HnswIndex) -> Result<(), VectorMemoryError> {
+        let sidecar = Self::hnsw_path(path);
+        std::fs::write(sidecar, serde_json::to_string(index)?)?;
         Ok(())
     }
 
-    fn build_vector_matrix(entries: &[MemoryEntry]) -> Option<Array2<f32>> {
-        if entries.is_empty() {
-            return None;
-        }
-        let dim = entries[0].vector.len();
-        if dim == 0 {
-            return None;
+    /// Lowercase, punctuation-stripped whitespace tokenization shared by
+    /// indexing and querying so BM25 term lookups match consistently.
+    fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_lowercase())
+            .collect()
+    }
+
+    /// BM25 score of `query_terms` against every entry's `text`, using the
+    /// in-memory corpus for document frequencies and average document length.
+    fn bm25_scores(entries: &[MemoryEntry], query_terms: &[String]) -> Vec<f32> {
+        let docs: Vec<Vec<String>> = entries.iter().map(|e| Self::tokenize(&e.text)).collect();
+        let n = docs.len();
+        if n == 0 {
+            return Vec::new();
         }
-        let mut matrix = Array2::zeros((entries.len(), dim));
-        for (i, entry) in entries.iter().enumerate() {
-            for (j, &val) in entry.vector.iter().enumerate() {
-                matrix[[i, j]] = val;
+        let doc_lens: Vec<usize> = docs.iter().map(|d| d.len()).collect();
+        let avgdl = doc_lens.iter().sum::<usize>() as f32 / n as f32;
+
+        let mut df: HashMap<String, usize> = HashMap::new();
+        for doc in &docs {
+            let unique: std::collections::HashSet<&String> = doc.iter().collect();
+            for term in unique {
+                *df.entry(term.clone()).or_insert(0) += 1;
             }
         }
-        for mut row in matrix.rows_mut() {
-            let norm: f32 = row.iter().map(|x| x * x).sum::<f32>().sqrt();
-            if norm > 0.0 {
-                row.mapv_inplace(|x| x / norm);
-            }
+
+        let idf: HashMap<String, f32> = query_terms
+            .iter()
+            .map(|t| {
+                let df_t = *df.get(t).unwrap_or(&0) as f32;
+                let idf = ((n as f32 - df_t + 0.5) / (df_t + 0.5) + 1.0).ln();
+                (t.clone(), idf)
+            })
+            .collect();
+
+        docs.iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                let dl = doc_lens[i] as f32;
+                let mut tf: HashMap<&str, u32> = HashMap::new();
+                for term in doc {
+                    *tf.entry(term.as_str()).or_insert(0) += 1;
+                }
+                query_terms
+                    .iter()
+                    .map(|t| {
+                        let tf_t = *tf.get(t.as_str()).unwrap_or(&0) as f32;
+                        if tf_t == 0.0 {
+                            return 0.0;
+                        }
+                        let idf_t = *idf.get(t).unwrap_or(&0.0);
+                        idf_t * (tf_t * (BM25_K1 + 1.0))
+                            / (tf_t + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl.max(1e-6)))
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Descending `(index, score)` ranking over a flat score list.
+    fn rank_by_score(scores: &[f32]) -> Vec<(usize, f32)> {
+        let mut ranked: Vec<(usize, f32)> = scores.iter().enumerate().map(|(i, &s)| (i, s)).collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    /// Min-max normalize a score list to `[0, 1]`; a flat list normalizes to all zeros.
+    fn min_max_normalize(scores: &[f32]) -> Vec<f32> {
+        let min = scores.iter().cloned().fold(f32::INFINITY, f32::min);
+        let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        if max - min < 1e-9 {
+            return vec![0.0; scores.len()];
         }
-        Some(matrix)
+        scores.iter().map(|&s| (s - min) / (max - min)).collect()
     }
 
-    fn cosine_similarity(vectors: &Array2<f32>, query: &Array1<f32>) -> Array1<f32> {
-        let norm: f32 = query.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let query_norm = if norm > 0.0 { query.mapv(|x| x / norm) } else { query.clone() };
-        vectors.dot(&query_norm)
+    /// Fuse cosine and BM25 scores into a single descending `(index, score)`
+    /// ranking. With `semantic_ratio`, linearly interpolates min-max
+    /// normalized scores; otherwise fuses by reciprocal rank (`RRF_K`).
+    fn fuse_hybrid(cosine: &[f32], bm25: &[f32], semantic_ratio: Option<f64>) -> Vec<(usize, f64)> {
+        let n = cosine.len();
+        let mut fused: Vec<(usize, f64)> = match semantic_ratio {
+            Some(ratio) => {
+                let cos_norm = Self::min_max_normalize(cosine);
+                let bm25_norm = Self::min_max_normalize(bm25);
+                (0..n)
+                    .map(|i| (i, ratio * cos_norm[i] as f64 + (1.0 - ratio) * bm25_norm[i] as f64))
+                    .collect()
+            }
+            None => {
+                let mut rrf_scores = vec![0.0f64; n];
+                for (rank, (idx, _)) in Self::rank_by_score(cosine).into_iter().enumerate() {
+                    rrf_scores[idx] += 1.0 / (RRF_K + rank as f64 + 1.0);
+                }
+                for (rank, (idx, _)) in Self::rank_by_score(bm25).into_iter().enumerate() {
+                    rrf_scores[idx] += 1.0 / (RRF_K + rank as f64 + 1.0);
+                }
+                rrf_scores.into_iter().enumerate().collect()
+            }
+        };
+        fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        fused
     }
 
     async fn embed_internal(client: &Client, api_base: &str, model: &str, text: &str) -> Result<Vec<f32>, VectorMemoryError> {
@@ -173,39 +674,102 @@ impl VectorMemory {
         embed_response.data.into_iter().next().map(|d| d.embedding)
             .ok_or_else(|| VectorMemoryError::EmbeddingApiError("No embedding in response".to_string()))
     }
+
+    /// Embed `text` through `embedder` if one is registered, otherwise fall
+    /// back to the raw `api_base`/`model` HTTP call `embed_internal` always
+    /// did. This is the single entry point `embed`/`store`/`search` share.
+    async fn embed_via(client: &Client, embedder: &Embedder, api_base: &str, model: &str, text: &str) -> Result<Vec<f32>, VectorMemoryError> {
+        match embedder {
+            Embedder::Raw => Self::embed_internal(client, api_base, model, text).await,
+            Embedder::Routed(backend) => {
+                let texts = vec![text.to_string()];
+                let mut vectors = backend
+                    .embed(model, &texts, None, None)
+                    .await
+                    .map_err(|e| VectorMemoryError::EmbeddingApiError(e.to_string()))?;
+                vectors
+                    .pop()
+                    .ok_or_else(|| VectorMemoryError::EmbeddingApiError("No embedding in response".to_string()))
+            }
+        }
+    }
+
+    /// Build the `InferenceBackend` named by `backend`, pointed at `api_base`.
+    fn make_backend(backend: &str, api_base: String, api_key: Option<String>, client: Client) -> Result<Arc<dyn InferenceBackend>, VectorMemoryError> {
+        match backend {
+            "ollama" => Ok(Arc::new(OllamaBackend::new(api_base, client))),
+            "llamacpp" => Ok(Arc::new(LlamaCppBackend::new(api_base, client))),
+            "openai" => Ok(Arc::new(OpenAICompatBackend::new(api_base, api_key, client))),
+            other => Err(VectorMemoryError::EmbeddingApiError(format!(
+                "Unknown embedding backend '{other}' (expected 'ollama', 'llamacpp', or 'openai')"
+            ))),
+        }
+    }
 }
 
 #[pymethods]
 impl VectorMemory {
     #[new]
-    #[pyo3(signature = (persistence_path=None))]
-    fn new(persistence_path: Option<String>) -> PyResult<Self> {
-        let path = PathBuf::from(persistence_path.unwrap_or_else(|| DEFAULT_MEMORY_PATH.to_string()));
-        let entries = Self::load_from_disk(&path).map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
-        let vectors = Self::build_vector_matrix(&entries);
-        let inner = VectorMemoryInner { entries, vectors, api_base: None, embed_model: None, persistence_path: path };
+    #[pyo3(signature = (persistence_path=None, m=None, ef_construction=None, ef_search=None, quantization=None, rescore_k=None))]
+    fn new(persistence_path: Option<String>, m: Option<usize>, ef_construction: Option<usize>, ef_search: Option<usize>, quantization: Option<String>, rescore_k: Option<usize>) -> PyResult<Self> {
+        let uri = persistence_path.unwrap_or_else(|| DEFAULT_MEMORY_PATH.to_string());
+        let (backend, file_path) = stores::select_backend(&uri).map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+        let sidecar_path = file_path.as_ref().map(Self::hnsw_path);
+        let entries = pyo3_async_runtimes::tokio::get_runtime()
+            .block_on(backend.load())
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+        let hnsw_m = m.unwrap_or(DEFAULT_HNSW_M).max(1);
+        let hnsw_ef_construction = ef_construction.unwrap_or(DEFAULT_HNSW_EF_CONSTRUCTION).max(1);
+        let hnsw_ef_search = ef_search.unwrap_or(DEFAULT_HNSW_EF_SEARCH).max(1);
+        let rescore_k = rescore_k.unwrap_or(DEFAULT_RESCORE_K).max(1);
+        let quantization = QuantizationMode::parse(quantization.as_deref().unwrap_or("none"))?;
+        let norm_vectors: Vec<Vec<f32>> = entries.iter().map(|e| Self::normalize(&e.vector)).collect();
+        let hnsw = Self::load_or_rebuild_hnsw(sidecar_path.as_ref(), &entries, &norm_vectors, hnsw_m, hnsw_ef_construction);
+        let (scalar_codec, scalar_codes, binary_codes) = Self::build_quantization(&entries, quantization);
+        let inner = VectorMemoryInner {
+            entries, norm_vectors, hnsw, dead_head: 0, api_base: None, embed_model: None, embedder: Embedder::default(),
+            quantization, scalar_codec, scalar_codes, binary_codes, backend, sidecar_path,
+        };
         Ok(Self {
             inner: Arc::new(RwLock::new(inner)),
             http_client: Client::builder().pool_max_idle_per_host(10).build().unwrap(),
             async_lock: Arc::new(TokioMutex::new(())),
+            hnsw_m,
+            hnsw_ef_construction,
+            hnsw_ef_search,
+            rescore_k,
         })
     }
 
-    fn set_embedder(&self, api_base: String, model_id: String) {
+    /// Configure how `store`/`search`/`embed` turn text into vectors.
+    /// With `backend` unset, hits `api_base`'s `/v1/embeddings` directly
+    /// (the historical behavior). With `backend` set to `"ollama"`,
+    /// `"llamacpp"`, or `"openai"`, routes embedding calls through that
+    /// `InferenceBackend` instead, built on this `VectorMemory`'s own HTTP
+    /// client (not shared with `InferenceRouter`) and using `api_key` for auth.
+    #[pyo3(signature = (api_base, model_id, backend=None, api_key=None))]
+    fn set_embedder(&self, api_base: String, model_id: String, backend: Option<String>, api_key: Option<String>) -> PyResult<()> {
+        let embedder = match backend {
+            Some(name) => Embedder::Routed(Self::make_backend(&name, api_base.clone(), api_key, self.http_client.clone())?),
+            None => Embedder::Raw,
+        };
         let mut inner = self.inner.write();
         inner.api_base = Some(api_base);
         inner.embed_model = Some(model_id);
+        inner.embedder = embedder;
+        Ok(())
     }
 
     fn embed<'py>(&self, py: Python<'py>, text: String) -> PyResult<Bound<'py, PyAny>> {
-        let (api_base, model) = {
+        let (api_base, model, embedder) = {
             let inner = self.inner.read();
             (inner.api_base.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?,
-             inner.embed_model.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?)
+             inner.embed_model.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?,
+             inner.embedder.clone())
         };
         let client = self.http_client.clone();
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            Self::embed_internal(&client, &api_base, &model, &text).await.map_err(|e| e.into())
+            Self::embed_via(&client, &embedder, &api_base, &model, &text).await.map_err(|e| e.into())
         })
     }
 
@@ -218,14 +782,17 @@ impl VectorMemory {
         let inner_arc = self.inner.clone();
         let client = self.http_client.clone();
         let async_lock = self.async_lock.clone();
+        let hnsw_m = self.hnsw_m;
+        let hnsw_ef_construction = self.hnsw_ef_construction;
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
             let _guard = async_lock.lock().await;
-            let (api_base, model) = {
+            let (api_base, model, embedder) = {
                 let inner = inner_arc.read();
                 (inner.api_base.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?,
-                 inner.embed_model.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?)
+                 inner.embed_model.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?,
+                 inner.embedder.clone())
             };
-            let vector = Self::embed_internal(&client, &api_base, &model, &text).await?;
+            let vector = Self::embed_via(&client, &embedder, &api_base, &model, &text).await?;
             let entry = MemoryEntry {
                 text, vector, tags, timestamp: Utc::now().timestamp_millis() as f64 / 1000.0,
                 source: source.unwrap_or_else(|| "internal".to_string()),
@@ -233,48 +800,194 @@ impl VectorMemory {
                 valid_from: valid_from.unwrap_or(0.0), valid_to: valid_to.unwrap_or(0.0),
                 entity_type: entity_type.unwrap_or_default(), entity_id: entity_id.unwrap_or_default(),
             };
-            let index = {
+
+            let (backend, prev_live_len) = {
+                let inner = inner_arc.read();
+                (inner.backend.clone(), inner.entries.len() - inner.dead_head)
+            };
+            backend.store(entry.clone()).await.map_err(VectorMemoryError::from)?;
+            // The backend enforces its own FIFO capacity, so the count it
+            // reports back tells us whether this store also evicted the
+            // oldest entry: growth by one means no eviction, anything less
+            // means one did.
+            let new_len = backend.count().await.map_err(VectorMemoryError::from)?;
+            let evicted = new_len <= prev_live_len;
+
+            // Always append the new entry and insert it into the graph
+            // incrementally; node indices only change at a full resync, which
+            // rebuilds the graph as well, so an append never invalidates
+            // existing HNSW neighbor links. If this store also
+            // evicted the oldest live entry, tombstone it in place instead of
+            // reindexing everything, and only pay for a full backend resync
+            // + graph rebuild once tombstones pile up past
+            // INDEX_COMPACTION_DEAD_RATIO — checked without holding the lock
+            // across the resync's own await point.
+            let needs_compaction = {
                 let mut inner = inner_arc.write();
-                while inner.entries.len() >= MAX_MEMORY_ENTRIES { inner.entries.remove(0); }
+                let ml = 1.0 / (hnsw_m as f64).ln();
+                let norm_vector = Self::normalize(&entry.vector);
                 inner.entries.push(entry);
-                inner.vectors = Self::build_vector_matrix(&inner.entries);
-                let _ = Self::save_to_disk(&inner.persistence_path, &inner.entries);
+                inner.norm_vectors.push(norm_vector);
+                inner.hnsw.insert(&inner.norm_vectors, hnsw_m, hnsw_ef_construction, ml);
+                if evicted {
+                    inner.dead_head += 1;
+                }
+                inner.dead_head as f64 / inner.entries.len() as f64 > INDEX_COMPACTION_DEAD_RATIO
+            };
+            if needs_compaction {
+                let entries = backend.load().await.map_err(VectorMemoryError::from)?;
+                let mut inner = inner_arc.write();
+                let ml = 1.0 / (hnsw_m as f64).ln();
+                inner.norm_vectors = entries.iter().map(|e| Self::normalize(&e.vector)).collect();
+                inner.entries = entries;
+                inner.dead_head = 0;
+                inner.hnsw = HnswIndex::default();
+                for i in 0..inner.norm_vectors.len() {
+                    inner.hnsw.insert(&inner.norm_vectors[..=i], hnsw_m, hnsw_ef_construction, ml);
+                }
+            }
+
+            let index = {
+                let mut inner = inner_arc.write();
+                let quantization = inner.quantization;
+                let (scalar_codec, scalar_codes, binary_codes) = Self::build_quantization(&inner.entries, quantization);
+                inner.scalar_codec = scalar_codec;
+                inner.scalar_codes = scalar_codes;
+                inner.binary_codes = binary_codes;
+
+                if let Some(sidecar) = inner.sidecar_path.clone() {
+                    let _ = Self::save_hnsw(&sidecar, &inner.hnsw);
+                }
                 inner.entries.len() - 1
             };
             Ok(index)
         })
     }
 
-    #[pyo3(signature = (query, top_k=None, temporal_filter=None))]
-    fn search<'py>(&self, py: Python<'py>, query: String, top_k: Option<usize>, temporal_filter: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+    /// Search by cosine similarity (`mode="vector"`, the default), BM25
+    /// keyword overlap (`mode="keyword"`), or both fused together
+    /// (`mode="hybrid"`). In hybrid mode, `semantic_ratio` (0.0-1.0)
+    /// linearly interpolates normalized cosine/BM25 scores; omitted, the
+    /// two rankings are fused by reciprocal rank instead. The temporal
+    /// filter and the `tags`/`entity_type`/`entity_id`/`source` metadata
+    /// filter (see `MetadataFilter`) are both applied after fusion, then
+    /// results are truncated to `top_k`.
+    #[pyo3(signature = (query, top_k=None, temporal_filter=None, mode=None, semantic_ratio=None, tags=None, entity_type=None, entity_id=None, source=None))]
+    fn search<'py>(&self, py: Python<'py>, query: String, top_k: Option<usize>, temporal_filter: Option<String>, mode: Option<String>, semantic_ratio: Option<f64>,
+        tags: Option<Vec<String>>, entity_type: Option<String>, entity_id: Option<String>, source: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
         let top_k = top_k.unwrap_or(5);
+        let mode = mode.unwrap_or_else(|| "vector".to_string());
+        if !matches!(mode.as_str(), "vector" | "keyword" | "hybrid") {
+            return Err(VectorMemoryError::InvalidMode(mode).into());
+        }
+        let metadata_filter = MetadataFilter { tags: tags.unwrap_or_default(), entity_type, entity_id, source };
         let inner_arc = self.inner.clone();
         let client = self.http_client.clone();
+        let hnsw_ef_search = self.hnsw_ef_search;
+        let rescore_k = self.rescore_k;
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let (api_base, model, entries, vectors) = {
+            let (api_base, model, embedder, entries, norm_vectors, hnsw, quantization, scalar_codec, scalar_codes, binary_codes, dead_head) = {
                 let inner = inner_arc.read();
-                (inner.api_base.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?,
-                 inner.embed_model.clone().ok_or(VectorMemoryError::EmbedderNotConfigured)?,
-                 inner.entries.clone(), inner.vectors.clone())
+                (inner.api_base.clone(), inner.embed_model.clone(), inner.embedder.clone(), inner.entries.clone(), inner.norm_vectors.clone(), inner.hnsw.clone(),
+                 inner.quantization, inner.scalar_codec.clone(), inner.scalar_codes.clone(), inner.binary_codes.clone(), inner.dead_head)
             };
-            if entries.is_empty() { return Ok(Vec::<(HashMap<String, String>, f64)>::new()); }
-            let vectors = match vectors { Some(v) => v, None => return Ok(Vec::new()) };
-            let query_vec = Self::embed_internal(&client, &api_base, &model, &query).await?;
-            let query_arr = Array1::from_vec(query_vec);
-            let similarities = Self::cosine_similarity(&vectors, &query_arr);
-            let mut scored: Vec<(usize, f32)> = similarities.iter().enumerate().map(|(i, &s)| (i, s)).collect();
-            scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-            let now = Utc::now().timestamp_millis() as f64 / 1000.0;
-            let filtered: Vec<(usize, f32)> = if let Some(ref filter) = temporal_filter {
-                scored.into_iter().filter(|(i, _)| {
-                    let e = &entries[*i];
-                    match filter.as_str() {
-                        "current" => (e.valid_from == 0.0 || e.valid_from <= now) && (e.valid_to == 0.0 || e.valid_to >= now),
-                        "historical" => e.valid_to > 0.0 && e.valid_to < now,
-                        _ => true,
+            if entries.len() <= dead_head { return Ok(Vec::<(HashMap<String, String>, f64)>::new()); }
+
+            // Brute-force cosine is exact and cheap for tiny corpora; past
+            // HNSW_EXACT_THRESHOLD (and with quantization off), fall through
+            // to the approximate graph so search stays sub-linear as the
+            // corpus grows toward the cap. The quantized prefilter-and-rescore
+            // path below is independent of corpus size (it doesn't touch the
+            // HNSW graph at all), so it stays in effect across the full
+            // 0..MAX_MEMORY_ENTRIES range whenever quantization is enabled,
+            // rather than only below HNSW_EXACT_THRESHOLD.
+            let cosine_scores: Option<Vec<f32>> = if mode != "keyword" {
+                let api_base = api_base.ok_or(VectorMemoryError::EmbedderNotConfigured)?;
+                let model = model.ok_or(VectorMemoryError::EmbedderNotConfigured)?;
+                let query_vec = Self::embed_via(&client, &embedder, &api_base, &model, &query).await?;
+                let query_norm = Self::normalize(&query_vec);
+                if quantization == QuantizationMode::None {
+                    if entries.len() <= HNSW_EXACT_THRESHOLD {
+                        Some(norm_vectors.iter().map(|v| Self::vec_dot(v, &query_norm)).collect())
+                    } else {
+                        let hits = hnsw.search(&norm_vectors, &query_norm, hnsw_ef_search.max(top_k), hnsw_ef_search);
+                        // Entries the ANN beam didn't surface rank just below the
+                        // weakest hit, so they sort last without poisoning fusion
+                        // math (an infinite sentinel would turn into NaN there).
+                        let floor = hits.iter().map(|&(_, s)| s).fold(f32::INFINITY, f32::min);
+                        let floor = if floor.is_finite() { floor - 1.0 } else { 0.0 };
+                        let mut scores = vec![floor; entries.len()];
+                        for (i, sim) in hits {
+                            scores[i] = sim;
+                        }
+                        Some(scores)
+                    }
+                } else {
+                    // Rank every entry by its cheap quantized approximation,
+                    // then re-score only the top `rescore_k` candidates with
+                    // exact cosine similarity against the full f32 vectors.
+                    let prefilter: Vec<(usize, f32)> = match quantization {
+                        QuantizationMode::Scalar => match &scalar_codec {
+                            Some(codec) => {
+                                let query_scaled: Vec<f32> = query_norm.iter().zip(&codec.scales).map(|(&q, &s)| q * s).collect();
+                                let base: f32 = query_norm.iter().zip(&codec.mins).zip(&codec.scales).map(|((&q, &mn), &sc)| q * (mn + 128.0 * sc)).sum();
+                                (0..entries.len()).map(|i| (i, base + Self::scalar_code_dot(&query_scaled, &scalar_codes[i]))).collect()
+                            }
+                            None => (0..entries.len()).map(|i| (i, Self::vec_dot(&norm_vectors[i], &query_norm))).collect(),
+                        },
+                        QuantizationMode::Binary => {
+                            let query_bits = Self::binary_encode(&query_norm);
+                            (0..entries.len()).map(|i| (i, -(Self::hamming_distance(&binary_codes[i], &query_bits) as f32))).collect()
+                        }
+                        QuantizationMode::None => unreachable!("handled by the branch above"),
+                    };
+                    let mut candidates = Self::rank_by_score(&prefilter.iter().map(|&(_, s)| s).collect::<Vec<_>>());
+                    candidates.truncate(rescore_k.max(top_k));
+                    let rescored: Vec<(usize, f32)> = candidates
+                        .into_iter()
+                        .map(|(i, _)| (i, Self::vec_dot(&norm_vectors[i], &query_norm)))
+                        .collect();
+
+                    let floor = rescored.iter().map(|&(_, s)| s).fold(f32::INFINITY, f32::min);
+                    let floor = if floor.is_finite() { floor - 1.0 } else { 0.0 };
+                    let mut scores = vec![floor; entries.len()];
+                    for (i, sim) in rescored {
+                        scores[i] = sim;
                     }
-                }).collect()
-            } else { scored };
+                    Some(scores)
+                }
+            } else { None };
+
+            let bm25_scores: Option<Vec<f32>> = if mode != "vector" {
+                let query_terms = Self::tokenize(&query);
+                Some(Self::bm25_scores(&entries, &query_terms))
+            } else { None };
+
+            let ranked: Vec<(usize, f64)> = match mode.as_str() {
+                "vector" => Self::rank_by_score(&cosine_scores.unwrap()).into_iter().map(|(i, s)| (i, s as f64)).collect(),
+                "keyword" => Self::rank_by_score(&bm25_scores.unwrap()).into_iter().map(|(i, s)| (i, s as f64)).collect(),
+                _ => Self::fuse_hybrid(&cosine_scores.unwrap(), &bm25_scores.unwrap(), semantic_ratio),
+            };
+
+            let now = Utc::now().timestamp_millis() as f64 / 1000.0;
+            let filtered: Vec<(usize, f64)> = ranked.into_iter().filter(|(i, _)| {
+                // Entries below dead_head were evicted by a since-tombstoned
+                // store and are only still present pending the next index
+                // compaction; never surface them.
+                if *i < dead_head {
+                    return false;
+                }
+                let e = &entries[*i];
+                if !metadata_filter.matches(e) {
+                    return false;
+                }
+                match temporal_filter.as_deref() {
+                    Some("current") => (e.valid_from == 0.0 || e.valid_from <= now) && (e.valid_to == 0.0 || e.valid_to >= now),
+                    Some("historical") => e.valid_to > 0.0 && e.valid_to < now,
+                    _ => true,
+                }
+            }).collect();
             let results: Vec<(HashMap<String, String>, f64)> = filtered.into_iter().take(top_k).map(|(i, score)| {
                 let e = &entries[i];
                 let mut map = HashMap::new();
@@ -282,19 +995,112 @@ impl VectorMemory {
                 map.insert("tags".to_string(), e.tags.clone());
                 map.insert("timestamp".to_string(), e.timestamp.to_string());
                 map.insert("source".to_string(), e.source.clone());
-                (map, score as f64)
+                (map, score)
             }).collect();
             Ok(results)
         })
     }
 
-    fn count(&self) -> usize { self.inner.read().entries.len() }
+    fn count(&self) -> usize {
+        let inner = self.inner.read();
+        inner.entries.len() - inner.dead_head
+    }
 
     fn clear(&self) -> PyResult<()> {
         let mut inner = self.inner.write();
         inner.entries.clear();
-        inner.vectors = None;
-        if inner.persistence_path.exists() { std::fs::remove_file(&inner.persistence_path)?; }
+        inner.norm_vectors.clear();
+        inner.hnsw = HnswIndex::default();
+        inner.dead_head = 0;
+        inner.scalar_codec = None;
+        inner.scalar_codes.clear();
+        inner.binary_codes.clear();
+        pyo3_async_runtimes::tokio::get_runtime()
+            .block_on(inner.backend.clear())
+            .map_err(|e: MemoryBackendError| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+        if let Some(ref sidecar) = inner.sidecar_path {
+            if sidecar.exists() { std::fs::remove_file(sidecar)?; }
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str, vector: Vec<f32>) -> MemoryEntry {
+        MemoryEntry {
+            text: text.to_string(),
+            vector,
+            tags: String::new(),
+            timestamp: 0.0,
+            source: String::new(),
+            temporal_type: String::new(),
+            valid_from: 0.0,
+            valid_to: 0.0,
+            entity_type: String::new(),
+            entity_id: String::new(),
+        }
+    }
+
+    // chunk3-2: HNSW insert/search should stay connected enough that every
+    // inserted point is findable, and return the exact match first when the
+    // query equals a stored vector.
+    #[test]
+    fn hnsw_search_finds_exact_match_and_reaches_every_node() {
+        let raw_vectors: Vec<Vec<f32>> = vec![
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+            vec![0.7, 0.7, 0.0, 0.0],
+            vec![0.0, 0.7, 0.7, 0.0],
+        ];
+        let norm_vectors: Vec<Vec<f32>> = raw_vectors.iter().map(|v| VectorMemory::normalize(v)).collect();
+        let mut hnsw = HnswIndex::default();
+        for i in 0..norm_vectors.len() {
+            hnsw.insert(&norm_vectors[..=i], 16, 100, 1.0 / (2.0f64).ln());
+        }
+
+        for (i, query) in norm_vectors.iter().enumerate() {
+            let hits = hnsw.search(&norm_vectors, query, 1, norm_vectors.len());
+            assert_eq!(hits.first().map(|&(idx, _)| idx), Some(i), "query {i} should retrieve itself as the top hit");
+        }
+    }
+
+    // chunk3-5: `dead_head` must shrink the reported count so tombstoned
+    // entries aren't counted as live.
+    #[test]
+    fn dead_head_hides_tombstoned_entries_from_count() {
+        let entries = vec![entry("evicted", vec![1.0, 0.0]), entry("b", vec![0.0, 1.0]), entry("c", vec![1.0, 1.0])];
+        let norm_vectors: Vec<Vec<f32>> = entries.iter().map(|e| VectorMemory::normalize(&e.vector)).collect();
+        let inner = VectorMemoryInner {
+            entries,
+            norm_vectors,
+            hnsw: HnswIndex::default(),
+            dead_head: 1,
+            api_base: None,
+            embed_model: None,
+            embedder: Embedder::default(),
+            quantization: QuantizationMode::None,
+            scalar_codec: None,
+            scalar_codes: Vec::new(),
+            binary_codes: Vec::new(),
+            backend: Arc::new(stores::MemoryStore::default()),
+            sidecar_path: None,
+        };
+        let mem = VectorMemory {
+            inner: Arc::new(RwLock::new(inner)),
+            http_client: Client::new(),
+            async_lock: Arc::new(TokioMutex::new(())),
+            hnsw_m: 16,
+            hnsw_ef_construction: 100,
+            hnsw_ef_search: 50,
+            rescore_k: 50,
+        };
+        // One of the three entries is tombstoned (dead_head = 1), so only
+        // the two live ones should be counted.
+        assert_eq!(mem.count(), 2);
+    }
+}
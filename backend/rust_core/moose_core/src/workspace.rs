@@ -6,10 +6,13 @@ use chrono::Utc;
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use pyo3::prelude::*;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::telemetry;
+
 #[derive(Debug, Error)]
 pub enum WorkspaceError {
     #[error("Database error: {0}")]
@@ -24,12 +27,29 @@ impl From<WorkspaceError> for PyErr {
     }
 }
 
+/// A single Bayou-style operation in the workspace log.
+///
+/// Ops are ordered by `(wall_clock, replica_id)` while tentative, and by
+/// `committed_seq` once a total order has been assigned. `payload` carries
+/// the op-specific arguments as JSON so new op kinds don't need schema changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkspaceOp {
+    id: String,
+    mission_id: String,
+    replica_id: String,
+    wall_clock: String,
+    op_kind: String,
+    payload: String,
+    committed_seq: Option<i64>,
+}
+
 struct SharedWorkspaceInner { conn: Connection }
 
 #[pyclass]
 pub struct SharedWorkspace {
     inner: Arc<RwLock<SharedWorkspaceInner>>,
     cache: Arc<DashMap<String, Vec<HashMap<String, String>>>>,
+    replica_id: String,
 }
 
 impl SharedWorkspace {
@@ -38,46 +58,171 @@ impl SharedWorkspace {
             CREATE TABLE IF NOT EXISTS workspace_entries (
                 id TEXT PRIMARY KEY, mission_id TEXT NOT NULL, agent_id TEXT NOT NULL,
                 entry_type TEXT NOT NULL, title TEXT NOT NULL, content TEXT NOT NULL,
-                tags TEXT NOT NULL DEFAULT '[]', reference_list TEXT NOT NULL DEFAULT '[]', created_at TEXT NOT NULL
+                tags TEXT NOT NULL DEFAULT '[]', reference_list TEXT NOT NULL DEFAULT '[]', created_at TEXT NOT NULL,
+                causality_token TEXT NOT NULL DEFAULT '1'
             );
             CREATE INDEX IF NOT EXISTS idx_workspace_mission ON workspace_entries(mission_id);
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS workspace_fts USING fts5(id UNINDEXED, title, content);
+            CREATE TRIGGER IF NOT EXISTS workspace_fts_ai AFTER INSERT ON workspace_entries BEGIN
+                INSERT INTO workspace_fts(id, title, content) VALUES (new.id, new.title, new.content);
+            END;
+            CREATE TRIGGER IF NOT EXISTS workspace_fts_ad AFTER DELETE ON workspace_entries BEGIN
+                DELETE FROM workspace_fts WHERE id = old.id;
+            END;
+            CREATE TRIGGER IF NOT EXISTS workspace_fts_au AFTER UPDATE OF content ON workspace_entries BEGIN
+                DELETE FROM workspace_fts WHERE id = old.id;
+                INSERT INTO workspace_fts(id, title, content) VALUES (new.id, new.title, new.content);
+            END;
+
+            CREATE TABLE IF NOT EXISTS workspace_ops (
+                id TEXT PRIMARY KEY, mission_id TEXT NOT NULL, replica_id TEXT NOT NULL,
+                wall_clock TEXT NOT NULL, op_kind TEXT NOT NULL, payload TEXT NOT NULL,
+                committed_seq INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_ops_stamp ON workspace_ops(wall_clock, replica_id);
+            CREATE INDEX IF NOT EXISTS idx_ops_committed ON workspace_ops(committed_seq);
         "#)?;
         Ok(())
     }
-}
 
-#[pymethods]
-impl SharedWorkspace {
-    #[new]
-    #[pyo3(signature = (db_path=None))]
-    fn new(db_path: Option<String>) -> PyResult<Self> {
-        let db_path = db_path.unwrap_or_else(|| "backend/workspace.db".to_string());
-        let conn = Connection::open(&db_path)?;
-        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")?;
-        Self::init_schema(&conn)?;
-        Ok(Self { inner: Arc::new(RwLock::new(SharedWorkspaceInner { conn })), cache: Arc::new(DashMap::new()) })
+    /// Appends an op to the tentative suffix of the log. Does not touch `committed_seq`;
+    /// that's assigned by `checkpoint` once the op is known-stable.
+    fn append_op(conn: &Connection, mission_id: &str, replica_id: &str, op_kind: &str, payload: &serde_json::Value) -> Result<WorkspaceOp, WorkspaceError> {
+        let op = WorkspaceOp {
+            id: Uuid::new_v4().to_string()[..12].to_string(),
+            mission_id: mission_id.to_string(),
+            replica_id: replica_id.to_string(),
+            wall_clock: Utc::now().to_rfc3339(),
+            op_kind: op_kind.to_string(),
+            payload: payload.to_string(),
+            committed_seq: None,
+        };
+        conn.execute(
+            "INSERT INTO workspace_ops (id, mission_id, replica_id, wall_clock, op_kind, payload, committed_seq) VALUES (?1, ?2, ?3, ?4, ?5, ?6, NULL)",
+            params![op.id, op.mission_id, op.replica_id, op.wall_clock, op.op_kind, op.payload],
+        )?;
+        Ok(op)
     }
 
-    fn add(&self, mission_id: String, agent_id: String, entry_type: String, title: String, content: String) -> PyResult<String> {
-        let id = Uuid::new_v4().to_string()[..12].to_string();
-        let now = Utc::now().to_rfc3339();
-        let inner = self.inner.write();
-        inner.conn.execute(
-            "INSERT INTO workspace_entries (id, mission_id, agent_id, entry_type, title, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![id, mission_id, agent_id, entry_type, title, content, now],
+    /// Dependency check mirroring Bayou's per-op precondition: reject an `add`
+    /// whose target entry already exists. `clear_mission` and `update` ops have no check.
+    fn dependency_check_add(conn: &Connection, entry_id: &str) -> Result<bool, WorkspaceError> {
+        let exists: bool = conn.query_row("SELECT EXISTS(SELECT 1 FROM workspace_entries WHERE id = ?1)", params![entry_id], |row| row.get(0))?;
+        Ok(!exists)
+    }
+
+    /// Applies a single op's effect to `workspace_entries`. A failed dependency check
+    /// is a no-op merge procedure, matching Bayou semantics, rather than an error.
+    fn apply_op(conn: &Connection, op: &WorkspaceOp) -> Result<(), WorkspaceError> {
+        match op.op_kind.as_str() {
+            "add" => {
+                let v: serde_json::Value = serde_json::from_str(&op.payload)?;
+                let entry_id = v["id"].as_str().unwrap_or_default();
+                if Self::dependency_check_add(conn, entry_id)? {
+                    conn.execute(
+                        "INSERT INTO workspace_entries (id, mission_id, agent_id, entry_type, title, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![entry_id, v["mission_id"].as_str().unwrap_or_default(), v["agent_id"].as_str().unwrap_or_default(),
+                            v["entry_type"].as_str().unwrap_or_default(), v["title"].as_str().unwrap_or_default(),
+                            v["content"].as_str().unwrap_or_default(), op.wall_clock],
+                    )?;
+                }
+            }
+            "clear_mission" => {
+                let v: serde_json::Value = serde_json::from_str(&op.payload)?;
+                conn.execute("DELETE FROM workspace_entries WHERE mission_id = ?1", params![v["mission_id"].as_str().unwrap_or_default()])?;
+            }
+            "update" => {
+                let v: serde_json::Value = serde_json::from_str(&op.payload)?;
+                let entry_id = v["id"].as_str().unwrap_or_default();
+                conn.execute(
+                    "UPDATE workspace_entries SET content = ?1, causality_token = ?2 WHERE id = ?3",
+                    params![v["content"].as_str().unwrap_or_default(), v["token"].as_str().unwrap_or_default(), entry_id],
+                )?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Rolls `workspace_entries` back to the last committed state (nothing tentative
+    /// applied) and replays the merged log in stamp order, so two replicas that
+    /// exchange ops converge to an identical materialized state.
+    fn replay_from_committed(conn: &Connection) -> Result<(), WorkspaceError> {
+        conn.execute("DELETE FROM workspace_entries", [])?;
+        let mut stmt = conn.prepare(
+            "SELECT id, mission_id, replica_id, wall_clock, op_kind, payload, committed_seq FROM workspace_ops \
+             ORDER BY CASE WHEN committed_seq IS NULL THEN 1 ELSE 0 END, committed_seq ASC, wall_clock ASC, replica_id ASC"
         )?;
-        Ok(id)
+        let ops: Vec<WorkspaceOp> = stmt.query_map([], |row| {
+            Ok(WorkspaceOp {
+                id: row.get(0)?, mission_id: row.get(1)?, replica_id: row.get(2)?,
+                wall_clock: row.get(3)?, op_kind: row.get(4)?, payload: row.get(5)?, committed_seq: row.get(6)?,
+            })
+        })?.filter_map(|r| r.ok()).collect();
+        for op in &ops {
+            Self::apply_op(conn, op)?;
+        }
+        Ok(())
     }
 
-    #[pyo3(signature = (mission_id, agent_id=None, entry_type=None))]
-    fn query(&self, mission_id: String, agent_id: Option<String>, entry_type: Option<String>) -> PyResult<Vec<HashMap<String, String>>> {
-        let inner = self.inner.read();
+    /// Pulls every op `src` has that `dest` is missing, merges them into
+    /// `dest`'s local log, and whenever it took in at least one new op
+    /// (including one that sorts earlier than an already-applied tentative
+    /// op), replays `dest`'s merged log in stamp order. One-directional; the `sync` pymethod calls this both ways so
+    /// the pair actually converges rather than only one side catching up.
+    fn pull_ops(dest: &SharedWorkspace, src: &SharedWorkspace) -> Result<u64, WorkspaceError> {
+        let their_ops: Vec<WorkspaceOp> = {
+            let their_inner = src.inner.read();
+            let mut stmt = their_inner.conn.prepare("SELECT id, mission_id, replica_id, wall_clock, op_kind, payload, committed_seq FROM workspace_ops")?;
+            stmt.query_map([], |row| {
+                Ok(WorkspaceOp {
+                    id: row.get(0)?, mission_id: row.get(1)?, replica_id: row.get(2)?,
+                    wall_clock: row.get(3)?, op_kind: row.get(4)?, payload: row.get(5)?, committed_seq: row.get(6)?,
+                })
+            })?.filter_map(|r| r.ok()).collect()
+        };
+
+        let inner = dest.inner.write();
+        let mut needs_replay = false;
+        let mut max_local_tentative_stamp: Option<(String, String)> = inner.conn.query_row(
+            "SELECT wall_clock, replica_id FROM workspace_ops WHERE committed_seq IS NULL ORDER BY wall_clock DESC, replica_id DESC LIMIT 1",
+            [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+        let mut inserted = 0u64;
+        for op in &their_ops {
+            let already_have: bool = inner.conn.query_row("SELECT EXISTS(SELECT 1 FROM workspace_ops WHERE id = ?1)", params![op.id], |row| row.get(0))?;
+            if already_have { continue; }
+            inner.conn.execute(
+                "INSERT INTO workspace_ops (id, mission_id, replica_id, wall_clock, op_kind, payload, committed_seq) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![op.id, op.mission_id, op.replica_id, op.wall_clock, op.op_kind, op.payload, op.committed_seq],
+            )?;
+            inserted += 1;
+            if op.committed_seq.is_none() {
+                if let Some((ref stamp, ref rep)) = max_local_tentative_stamp {
+                    if (&op.wall_clock, &op.replica_id) < (stamp, rep) { needs_replay = true; }
+                }
+                max_local_tentative_stamp = Some(max_local_tentative_stamp.map_or(
+                    (op.wall_clock.clone(), op.replica_id.clone()),
+                    |(s, r)| if (op.wall_clock.clone(), op.replica_id.clone()) > (s.clone(), r.clone()) { (op.wall_clock.clone(), op.replica_id.clone()) } else { (s, r) },
+                ));
+            }
+        }
+        if inserted > 0 { needs_replay = true; }
+        if needs_replay {
+            dest.cache.clear();
+            Self::replay_from_committed(&inner.conn)?;
+        }
+        Ok(inserted)
+    }
+
+    fn query_rows(conn: &Connection, mission_id: &str, agent_id: Option<&str>, entry_type: Option<&str>) -> Result<Vec<HashMap<String, String>>, WorkspaceError> {
         let mut sql = "SELECT id, agent_id, entry_type, title, content FROM workspace_entries WHERE mission_id = ?1".to_string();
         if agent_id.is_some() { sql.push_str(" AND agent_id = ?2"); }
         if entry_type.is_some() { sql.push_str(if agent_id.is_some() { " AND entry_type = ?3" } else { " AND entry_type = ?2" }); }
         sql.push_str(" ORDER BY created_at ASC");
-        let mut stmt = inner.conn.prepare(&sql)?;
-        let results: Vec<HashMap<String, String>> = match (agent_id.as_ref(), entry_type.as_ref()) {
+        let mut stmt = conn.prepare(&sql)?;
+        let results: Vec<HashMap<String, String>> = match (agent_id, entry_type) {
             (Some(a), Some(t)) => stmt.query_map(params![mission_id, a, t], |row| {
                 let mut m = HashMap::new();
                 m.insert("id".to_string(), row.get::<_, String>(0)?);
@@ -117,7 +262,180 @@ impl SharedWorkspace {
         };
         Ok(results)
     }
+}
 
+#[pymethods]
+impl SharedWorkspace {
+    #[new]
+    #[pyo3(signature = (db_path=None, replica_id=None))]
+    fn new(db_path: Option<String>, replica_id: Option<String>) -> PyResult<Self> {
+        let db_path = db_path.unwrap_or_else(|| "backend/workspace.db".to_string());
+        let conn = Connection::open(&db_path)?;
+        conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA synchronous=NORMAL;")?;
+        Self::init_schema(&conn)?;
+        let replica_id = replica_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+        Ok(Self { inner: Arc::new(RwLock::new(SharedWorkspaceInner { conn })), cache: Arc::new(DashMap::new()), replica_id })
+    }
+
+    #[tracing::instrument(skip(self, title, content), fields(mission_id = %mission_id, agent_id = %agent_id))]
+    fn add(&self, mission_id: String, agent_id: String, entry_type: String, title: String, content: String) -> PyResult<String> {
+        let id = Uuid::new_v4().to_string()[..12].to_string();
+        let now = Utc::now().to_rfc3339();
+        let start = std::time::Instant::now();
+        let inner = self.inner.write();
+        inner.conn.execute(
+            "INSERT INTO workspace_entries (id, mission_id, agent_id, entry_type, title, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![id, mission_id, agent_id, entry_type, title, content, now],
+        )?;
+        telemetry::record_query_latency("workspace.add", start.elapsed());
+        let payload = serde_json::json!({
+            "id": id, "mission_id": mission_id, "agent_id": agent_id,
+            "entry_type": entry_type, "title": title, "content": content,
+        });
+        Self::append_op(&inner.conn, &mission_id, &self.replica_id, "add", &payload)?;
+        Ok(id)
+    }
+
+    #[pyo3(signature = (mission_id, agent_id=None, entry_type=None))]
+    #[tracing::instrument(skip(self), fields(mission_id = %mission_id))]
+    fn query(&self, mission_id: String, agent_id: Option<String>, entry_type: Option<String>) -> PyResult<Vec<HashMap<String, String>>> {
+        let start = std::time::Instant::now();
+        let inner = self.inner.read();
+        let results = Self::query_rows(&inner.conn, &mission_id, agent_id.as_deref(), entry_type.as_deref())?;
+        telemetry::record_query_latency("workspace.query", start.elapsed());
+        telemetry::record_rows_returned("workspace.query", results.len());
+        Ok(results)
+    }
+
+    /// Runs several `query`-shaped lookups under a single read-lock acquisition.
+    /// Each filter is a map with the same keys as `query`'s arguments
+    /// (`mission_id` required, `agent_id`/`entry_type` optional); results are
+    /// returned in the same order as `filters`.
+    #[tracing::instrument(skip(self, filters))]
+    fn query_batch(&self, filters: Vec<HashMap<String, String>>) -> PyResult<Vec<Vec<HashMap<String, String>>>> {
+        let start = std::time::Instant::now();
+        let inner = self.inner.read();
+        let mut batch_results = Vec::with_capacity(filters.len());
+        for filter in &filters {
+            let mission_id = filter.get("mission_id").cloned().unwrap_or_default();
+            let agent_id = filter.get("agent_id").map(String::as_str);
+            let entry_type = filter.get("entry_type").map(String::as_str);
+            batch_results.push(Self::query_rows(&inner.conn, &mission_id, agent_id, entry_type)?);
+        }
+        telemetry::record_query_latency("workspace.query_batch", start.elapsed());
+        Ok(batch_results)
+    }
+
+    /// Inserts several entries as a single SQLite transaction, appending one
+    /// op per entry to the log. Returns `(id, causality_token)` pairs in the
+    /// same order as `entries`.
+    #[tracing::instrument(skip(self, entries))]
+    fn add_batch(&self, entries: Vec<HashMap<String, String>>) -> PyResult<Vec<(String, String)>> {
+        let start = std::time::Instant::now();
+        let inner = self.inner.write();
+        inner.conn.execute_batch("BEGIN")?;
+        let mut results = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let mission_id = entry.get("mission_id").cloned().unwrap_or_default();
+            let agent_id = entry.get("agent_id").cloned().unwrap_or_default();
+            let entry_type = entry.get("entry_type").cloned().unwrap_or_default();
+            let title = entry.get("title").cloned().unwrap_or_default();
+            let content = entry.get("content").cloned().unwrap_or_default();
+            let id = Uuid::new_v4().to_string()[..12].to_string();
+            let now = Utc::now().to_rfc3339();
+            let token = "1".to_string();
+            if let Err(e) = inner.conn.execute(
+                "INSERT INTO workspace_entries (id, mission_id, agent_id, entry_type, title, content, created_at, causality_token) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![id, mission_id, agent_id, entry_type, title, content, now, token],
+            ) {
+                inner.conn.execute_batch("ROLLBACK")?;
+                return Err(WorkspaceError::from(e).into());
+            }
+            let payload = serde_json::json!({
+                "id": id, "mission_id": mission_id, "agent_id": agent_id,
+                "entry_type": entry_type, "title": title, "content": content,
+            });
+            if let Err(e) = Self::append_op(&inner.conn, &mission_id, &self.replica_id, "add", &payload) {
+                inner.conn.execute_batch("ROLLBACK")?;
+                return Err(e.into());
+            }
+            results.push((id, token));
+        }
+        inner.conn.execute_batch("COMMIT")?;
+        telemetry::record_query_latency("workspace.add_batch", start.elapsed());
+        Ok(results)
+    }
+
+    /// Optimistic-concurrency update: succeeds only if `token` matches the
+    /// entry's current `causality_token`, in which case the token is bumped
+    /// and `(true, content, new_token)` is returned. On a stale token the
+    /// entry is left untouched and `(false, current_content, current_token)`
+    /// is returned so the caller can re-read and retry.
+    fn update(&self, id: String, content: String, token: String) -> PyResult<(bool, String, String)> {
+        let inner = self.inner.write();
+        let current: Option<(String, String, String)> = inner.conn.query_row(
+            "SELECT content, causality_token, mission_id FROM workspace_entries WHERE id = ?1",
+            params![id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).optional()?;
+        let Some((current_content, current_token, mission_id)) = current else {
+            return Ok((false, String::new(), String::new()));
+        };
+        if current_token != token {
+            return Ok((false, current_content, current_token));
+        }
+        let next_token = current_token.parse::<u64>().map(|n| (n + 1).to_string()).unwrap_or_else(|_| Uuid::new_v4().to_string());
+        inner.conn.execute(
+            "UPDATE workspace_entries SET content = ?1, causality_token = ?2 WHERE id = ?3",
+            params![content, next_token, id],
+        )?;
+        let payload = serde_json::json!({ "id": id, "content": content, "token": next_token });
+        Self::append_op(&inner.conn, &mission_id, &self.replica_id, "update", &payload)?;
+        Ok((true, content, next_token))
+    }
+
+    #[tracing::instrument(skip(self), fields(mission_id = %mission_id))]
+    /// Ranked full-text recall over `title`+`content` within a mission, using FTS5
+    /// MATCH syntax and ordering by BM25 relevance. Pass `exact=True` for a plain
+    /// substring scan instead, mirroring `EpisodicMemory::search`.
+    #[pyo3(signature = (mission_id, query, top_k=None, exact=None))]
+    fn search(&self, mission_id: String, query: String, top_k: Option<usize>, exact: Option<bool>) -> PyResult<Vec<HashMap<String, String>>> {
+        let top_k = top_k.unwrap_or(10);
+        let inner = self.inner.read();
+        let results: Vec<HashMap<String, String>> = if exact.unwrap_or(false) {
+            let query_pattern = format!("%{}%", query);
+            let mut stmt = inner.conn.prepare(
+                "SELECT id, agent_id, entry_type, title, content FROM workspace_entries \
+                 WHERE mission_id = ?1 AND (title LIKE ?2 OR content LIKE ?2) ORDER BY created_at ASC LIMIT ?3"
+            )?;
+            stmt.query_map(params![mission_id, query_pattern, top_k as i64], |row| {
+                let mut m = HashMap::new();
+                m.insert("id".to_string(), row.get::<_, String>(0)?);
+                m.insert("agent_id".to_string(), row.get::<_, String>(1)?);
+                m.insert("entry_type".to_string(), row.get::<_, String>(2)?);
+                m.insert("title".to_string(), row.get::<_, String>(3)?);
+                m.insert("content".to_string(), row.get::<_, String>(4)?);
+                Ok(m)
+            })?.filter_map(|r| r.ok()).collect()
+        } else {
+            let mut stmt = inner.conn.prepare(
+                "SELECT e.id, e.agent_id, e.entry_type, e.title, e.content FROM workspace_fts f \
+                 JOIN workspace_entries e ON e.id = f.id \
+                 WHERE workspace_fts MATCH ?1 AND e.mission_id = ?2 ORDER BY bm25(workspace_fts) LIMIT ?3"
+            )?;
+            stmt.query_map(params![query, mission_id, top_k as i64], |row| {
+                let mut m = HashMap::new();
+                m.insert("id".to_string(), row.get::<_, String>(0)?);
+                m.insert("agent_id".to_string(), row.get::<_, String>(1)?);
+                m.insert("entry_type".to_string(), row.get::<_, String>(2)?);
+                m.insert("title".to_string(), row.get::<_, String>(3)?);
+                m.insert("content".to_string(), row.get::<_, String>(4)?);
+                Ok(m)
+            })?.filter_map(|r| r.ok()).collect()
+        };
+        Ok(results)
+    }
+
+    #[tracing::instrument(skip(self), fields(mission_id = %mission_id))]
     fn get_mission_summary(&self, mission_id: String) -> PyResult<String> {
         let entries = self.query(mission_id.clone(), None, None)?;
         if entries.is_empty() { return Ok(format!("No entries for mission {}", mission_id)); }
@@ -125,6 +443,7 @@ impl SharedWorkspace {
         for e in entries {
             summary.push_str(&format!("### {} ({})\n{}\n\n", e.get("title").unwrap_or(&String::new()), e.get("agent_id").unwrap_or(&String::new()), e.get("content").unwrap_or(&String::new())));
         }
+        telemetry::record_summary_size(&mission_id, summary.len());
         Ok(summary)
     }
 
@@ -132,6 +451,8 @@ impl SharedWorkspace {
         self.cache.remove(&mission_id);
         let inner = self.inner.write();
         let count = inner.conn.execute("DELETE FROM workspace_entries WHERE mission_id = ?1", params![mission_id])?;
+        let payload = serde_json::json!({ "mission_id": mission_id });
+        Self::append_op(&inner.conn, &mission_id, &self.replica_id, "clear_mission", &payload)?;
         Ok(count as u64)
     }
 
@@ -147,4 +468,63 @@ impl SharedWorkspace {
         inner.conn.execute("DELETE FROM workspace_entries", [])?;
         Ok(())
     }
+
+    /// Anti-entropy sync: exchanges ops in both directions so `self` and
+    /// `other` converge to the same materialized state, rather than only
+    /// pulling `other`'s ops into `self` and leaving `other` behind.
+    /// Returns the total number of ops copied across both directions.
+    fn sync(&self, other: &SharedWorkspace) -> PyResult<u64> {
+        let pulled = Self::pull_ops(self, other)?;
+        let pushed = Self::pull_ops(other, self)?;
+        Ok(pulled + pushed)
+    }
+
+    /// Assigns a total order (`committed_seq`) to every currently-tentative op,
+    /// in stamp order. `replay_from_committed` treats committed ops as fixed
+    /// history and only needs to worry about ordering among the still-tentative
+    /// suffix, so checkpointing regularly keeps that suffix small.
+    ///
+    /// This does not truncate `workspace_ops` or take a separate snapshot:
+    /// `sync` exchanges raw ops (including committed ones) so a straggler
+    /// replica can always replay from the full log, and pruning committed
+    /// rows here would strand any replica that hasn't caught up yet. The log
+    /// therefore still grows unboundedly; bounding it would need `sync` to
+    /// exchange a materialized snapshot instead of the op log itself.
+    fn checkpoint(&self) -> PyResult<u64> {
+        let inner = self.inner.write();
+        let next_seq: i64 = inner.conn.query_row("SELECT COALESCE(MAX(committed_seq), 0) FROM workspace_ops", [], |row| row.get(0))?;
+        let mut stmt = inner.conn.prepare("SELECT id FROM workspace_ops WHERE committed_seq IS NULL ORDER BY wall_clock ASC, replica_id ASC")?;
+        let ids: Vec<String> = stmt.query_map([], |row| row.get(0))?.filter_map(|r| r.ok()).collect();
+        drop(stmt);
+        let mut seq = next_seq;
+        for id in &ids {
+            seq += 1;
+            inner.conn.execute("UPDATE workspace_ops SET committed_seq = ?1 WHERE id = ?2", params![seq, id])?;
+        }
+        Ok(ids.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // chunk1-1: a single sync() call should make both replicas converge,
+    // not just pull the callee's ops into the caller.
+    #[test]
+    fn sync_converges_both_replicas_in_one_call() {
+        let replica_a = SharedWorkspace::new(Some(":memory:".to_string()), Some("replica-a".to_string())).unwrap();
+        let replica_b = SharedWorkspace::new(Some(":memory:".to_string()), Some("replica-b".to_string())).unwrap();
+
+        replica_a.add("mission-1".to_string(), "agent-a".to_string(), "note".to_string(), "from a".to_string(), "hello from a".to_string()).unwrap();
+        replica_b.add("mission-1".to_string(), "agent-b".to_string(), "note".to_string(), "from b".to_string(), "hello from b".to_string()).unwrap();
+
+        replica_a.sync(&replica_b).unwrap();
+
+        let a_titles: Vec<String> = replica_a.query("mission-1".to_string(), None, None).unwrap().into_iter().map(|e| e["title"].clone()).collect();
+        let b_titles: Vec<String> = replica_b.query("mission-1".to_string(), None, None).unwrap().into_iter().map(|e| e["title"].clone()).collect();
+
+        assert!(a_titles.contains(&"from b".to_string()), "replica a should have pulled b's entry");
+        assert!(b_titles.contains(&"from a".to_string()), "replica b should have received a's entry in the same call");
+    }
 }